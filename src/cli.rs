@@ -0,0 +1,431 @@
+use crate::game::computer::computer_setup::check_fleet_fits;
+use crate::game::computer::computers::monte_carlo::MonteCarloSettings;
+use crate::game::export::ExportFormat;
+use crate::game::fleet::FleetDefinition;
+use crate::{BoardSize, ComputerAttackStrategy, Difficulty, GameMode};
+use std::time::Duration;
+
+pub const USAGE: &str = "Usage:
+  battleship                      Interactive menus
+  battleship play [options]       Play a game, --vs picks the opponent
+  battleship hotseat [options]    Pass and play against a friend
+  battleship simulate [options]   Let two computers fight
+  battleship tournament [options] Let every attack strategy fight every other
+  battleship benchmark [options]  Count the shots a strategy needs to sink a fleet
+  battleship placements [options] Count the shots every strategy needs to sink each kind of placement
+  battleship host [options]       Host a game over the network
+  battleship join <address>       Join a game hosted over the network
+  battleship replay <file>        Step through a recorded game
+
+Options:
+  --vs <computer|friend>          Opponent for play
+  --difficulty <level>            Computer difficulty: easy, medium, hard, expert or adaptive
+  --profile <name>                Player profile the adaptive computer learns from, default when not given
+  --p1 <strategy>                 Attack strategy of computer one
+  --p2 <strategy>                 Attack strategy of computer two
+  --strategy <strategy>           Attack strategy to benchmark
+  --samples <number>              Layouts monte-carlo and expert sample per shot, 500 when not given
+  --time-budget <ms>              Longest monte-carlo and expert sample for per shot, a seed then no longer replays a game exactly
+  --games <number>                Number of games to simulate, per match in a tournament, per pairing for placements
+  --size <N|WxH>                  Board size, from 6 to 26 on each side
+  --fleet <fleet>                 classic, hasbro-1990, russian or a list like \"Carrier:5,Cruiser:3\"
+  --record <file>                 Write every shot of the game to a record file
+  --output <file>                 Export simulation results to a .csv or .json file
+  --port <number>                 Port to host on, 7878 when not given
+  --seed <number>                 Seed for the computer's randomness, to replay a game or simulation
+  -h, --help                      Show this message
+
+Strategies: random, hunt-target, probability, density, monte-carlo, hacker
+Any value that is not given is asked for with the usual menus, except that simulate,
+tournament, benchmark and placements use the classic 10x10 board and fleet.";
+
+// values given on the command line, anything left empty is asked for interactively
+#[derive(Default)]
+pub struct CliOptions {
+    pub game_mode: Option<GameMode>,
+    // `play` without `--vs` still has to choose between a friend and the computer
+    pub play_only: bool,
+    pub difficulty: Option<Difficulty>,
+    pub profile: Option<String>,
+    pub attack_strategy_one: Option<ComputerAttackStrategy>,
+    pub attack_strategy_two: Option<ComputerAttackStrategy>,
+    pub benchmark_strategy: Option<ComputerAttackStrategy>,
+    pub monte_carlo_settings: MonteCarloSettings,
+    pub games_to_play: Option<i32>,
+    pub board_size: Option<BoardSize>,
+    pub fleet: Option<FleetDefinition>,
+    pub record_path: Option<String>,
+    pub output_path: Option<String>,
+    pub replay_path: Option<String>,
+    pub port: Option<u16>,
+    pub seed: Option<u64>,
+    pub join_address: Option<String>,
+    pub show_help: bool,
+}
+
+pub fn parse_args(args: &[String]) -> Result<CliOptions, String> {
+    let mut options = CliOptions::default();
+    let mut args = args.iter();
+
+    match args.next().map(|arg| arg.as_str()) {
+        None => return Ok(options),
+        Some("play") => options.play_only = true,
+        Some("hotseat") => options.game_mode = Some(GameMode::MultiPlayer),
+        Some("simulate") => options.game_mode = Some(GameMode::ComputerFight),
+        Some("tournament") => options.game_mode = Some(GameMode::Tournament),
+        Some("benchmark") => options.game_mode = Some(GameMode::Benchmark),
+        Some("placements") => options.game_mode = Some(GameMode::PlacementBenchmark),
+        Some("host") => options.game_mode = Some(GameMode::NetworkHost),
+        Some("join") => {
+            let address = args.next().ok_or("Missing address to join")?;
+            options.game_mode = Some(GameMode::NetworkJoin);
+            options.join_address = Some(address.to_string());
+            if let Some(arg) = args.next() {
+                return Err(format!("Unexpected argument \"{}\"", arg));
+            }
+            return Ok(options);
+        }
+        Some("replay") => {
+            let record_path = args.next().ok_or("Missing record file for replay")?;
+            options.replay_path = Some(record_path.to_string());
+            if let Some(arg) = args.next() {
+                return Err(format!("Unexpected argument \"{}\"", arg));
+            }
+            return Ok(options);
+        }
+        Some("help" | "-h" | "--help") => {
+            options.show_help = true;
+            return Ok(options);
+        }
+        Some(command) => return Err(format!("Unknown command \"{}\"", command)),
+    }
+
+    while let Some(flag) = args.next() {
+        if flag == "-h" || flag == "--help" {
+            options.show_help = true;
+            continue;
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", flag))?;
+
+        match flag.as_str() {
+            "--vs" => {
+                if !options.play_only {
+                    return Err("--vs can only be used with play".to_string());
+                }
+                options.game_mode = Some(parse_opponent(value)?);
+            }
+            "--difficulty" => options.difficulty = Some(parse_difficulty(value)?),
+            "--profile" => options.profile = Some(parse_profile(value)?),
+            "--p1" => options.attack_strategy_one = Some(parse_attack_strategy(value)?),
+            "--p2" => options.attack_strategy_two = Some(parse_attack_strategy(value)?),
+            "--strategy" => options.benchmark_strategy = Some(parse_attack_strategy(value)?),
+            "--samples" => {
+                options.monte_carlo_settings = options
+                    .monte_carlo_settings
+                    .set_sample_count(parse_samples(value)?);
+            }
+            "--time-budget" => {
+                options.monte_carlo_settings = options
+                    .monte_carlo_settings
+                    .set_time_budget(parse_time_budget(value)?);
+            }
+            "--games" => options.games_to_play = Some(parse_games(value)?),
+            "--size" => options.board_size = Some(parse_board_size(value)?),
+            "--fleet" => options.fleet = Some(parse_fleet(value)?),
+            "--record" => options.record_path = Some(value.to_string()),
+            "--output" => {
+                if !matches!(options.game_mode, Some(GameMode::ComputerFight)) {
+                    return Err("--output can only be used with simulate".to_string());
+                }
+                options.output_path = Some(parse_output_path(value)?);
+            }
+            "--port" => options.port = Some(parse_port(value)?),
+            "--seed" => options.seed = Some(parse_seed(value)?),
+            _ => return Err(format!("Unknown option \"{}\"", flag)),
+        }
+    }
+
+    // games between computers are often scripted, so they use the classic board and fleet rather
+    // than asking for them
+    if matches!(
+        options.game_mode,
+        Some(
+            GameMode::ComputerFight
+                | GameMode::Tournament
+                | GameMode::Benchmark
+                | GameMode::PlacementBenchmark
+        )
+    ) {
+        options.board_size.get_or_insert_with(BoardSize::default);
+        options.fleet.get_or_insert_with(FleetDefinition::classic);
+    }
+
+    if let (Some(board_size), Some(fleet)) = (options.board_size, &options.fleet) {
+        check_fleet_fits(board_size, fleet).map_err(|error| error.to_string())?;
+    }
+
+    Ok(options)
+}
+
+fn parse_opponent(value: &str) -> Result<GameMode, String> {
+    match value {
+        "computer" => Ok(GameMode::SinglePlayer),
+        "friend" => Ok(GameMode::MultiPlayer),
+        _ => Err(format!("Unknown opponent \"{}\"", value)),
+    }
+}
+
+fn parse_difficulty(value: &str) -> Result<Difficulty, String> {
+    match value {
+        "easy" => Ok(Difficulty::Easy),
+        "medium" => Ok(Difficulty::Medium),
+        "hard" => Ok(Difficulty::Hard),
+        "expert" => Ok(Difficulty::Expert(MonteCarloSettings::default())),
+        "adaptive" => Ok(Difficulty::Adaptive),
+        _ => Err(format!("Unknown difficulty \"{}\"", value)),
+    }
+}
+
+// profile names are stored as a single word in the heatmap file
+fn parse_profile(value: &str) -> Result<String, String> {
+    let valid = !value.is_empty()
+        && value
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || "-_".contains(character));
+
+    if valid {
+        Ok(value.to_string())
+    } else {
+        Err(format!(
+            "Invalid profile \"{}\", use letters, digits, - and _",
+            value
+        ))
+    }
+}
+
+fn parse_attack_strategy(value: &str) -> Result<ComputerAttackStrategy, String> {
+    match value {
+        "random" => Ok(ComputerAttackStrategy::Random),
+        "hunt-target" => Ok(ComputerAttackStrategy::HuntAndTarget),
+        "probability" => Ok(ComputerAttackStrategy::Probability),
+        "density" => Ok(ComputerAttackStrategy::ProbabilityDensity),
+        "monte-carlo" => Ok(ComputerAttackStrategy::MonteCarlo(
+            MonteCarloSettings::default(),
+        )),
+        "hacker" => Ok(ComputerAttackStrategy::Hacker),
+        _ => Err(format!("Unknown attack strategy \"{}\"", value)),
+    }
+}
+
+fn parse_games(value: &str) -> Result<i32, String> {
+    match value.parse::<i32>() {
+        Ok(games) if games >= 1 => Ok(games),
+        _ => Err(format!("Invalid number of games \"{}\"", value)),
+    }
+}
+
+fn parse_samples(value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(samples) if samples >= 1 => Ok(samples),
+        _ => Err(format!("Invalid number of samples \"{}\"", value)),
+    }
+}
+
+fn parse_time_budget(value: &str) -> Result<Duration, String> {
+    match value.parse::<u64>() {
+        Ok(milliseconds) if milliseconds >= 1 => Ok(Duration::from_millis(milliseconds)),
+        _ => Err(format!("Invalid time budget \"{}\"", value)),
+    }
+}
+
+fn parse_port(value: &str) -> Result<u16, String> {
+    match value.parse::<u16>() {
+        Ok(port) if port >= 1 => Ok(port),
+        _ => Err(format!("Invalid port \"{}\"", value)),
+    }
+}
+
+fn parse_seed(value: &str) -> Result<u64, String> {
+    value
+        .parse::<u64>()
+        .map_err(|_| format!("Invalid seed \"{}\"", value))
+}
+
+fn parse_output_path(value: &str) -> Result<String, String> {
+    match ExportFormat::from_path(value) {
+        Some(_) => Ok(value.to_string()),
+        None => Err(format!(
+            "Unknown output format \"{}\", use a .csv or .json file",
+            value
+        )),
+    }
+}
+
+fn parse_board_size(value: &str) -> Result<BoardSize, String> {
+    let invalid = || format!("Invalid board size \"{}\"", value);
+
+    let (width, height) = match value.split_once(['x', 'X']) {
+        Some((width, height)) => (width, height),
+        None => (value, value),
+    };
+
+    let board_size = BoardSize::new(
+        width.trim().parse().map_err(|_| invalid())?,
+        height.trim().parse().map_err(|_| invalid())?,
+    );
+
+    if board_size.is_valid() {
+        Ok(board_size)
+    } else {
+        Err(invalid())
+    }
+}
+
+fn parse_fleet(value: &str) -> Result<FleetDefinition, String> {
+    match value {
+        "classic" => Ok(FleetDefinition::classic()),
+        "hasbro-1990" => Ok(FleetDefinition::hasbro_1990()),
+        "russian" => Ok(FleetDefinition::russian()),
+        _ => FleetDefinition::parse("Custom", value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<CliOptions, String> {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        parse_args(&args)
+    }
+
+    #[test]
+    fn no_arguments_leave_everything_to_the_menus() {
+        let options = parse("").unwrap();
+
+        assert!(options.game_mode.is_none());
+        assert!(options.board_size.is_none());
+        assert!(options.fleet.is_none());
+    }
+
+    #[test]
+    fn simulate_reads_every_flag() {
+        let options = parse(
+            "simulate --p1 probability --p2 hunt-target --games 10000 --size 12x8 --fleet russian --seed 7 --output results.csv",
+        )
+        .unwrap();
+
+        assert!(matches!(options.game_mode, Some(GameMode::ComputerFight)));
+        assert_eq!(
+            options.attack_strategy_one,
+            Some(ComputerAttackStrategy::Probability)
+        );
+        assert_eq!(
+            options.attack_strategy_two,
+            Some(ComputerAttackStrategy::HuntAndTarget)
+        );
+        assert_eq!(options.games_to_play, Some(10000));
+        assert_eq!(options.board_size, Some(BoardSize::new(12, 8)));
+        assert_eq!(options.fleet, Some(FleetDefinition::russian()));
+        assert_eq!(options.seed, Some(7));
+        assert_eq!(options.output_path.as_deref(), Some("results.csv"));
+    }
+
+    #[test]
+    fn computer_games_default_to_the_classic_board_and_fleet() {
+        for command in ["simulate", "tournament", "benchmark", "placements"] {
+            let options = parse(command).unwrap();
+
+            assert_eq!(options.board_size, Some(BoardSize::default()));
+            assert_eq!(options.fleet, Some(FleetDefinition::classic()));
+        }
+
+        let options = parse("play --vs computer").unwrap();
+        assert!(options.board_size.is_none());
+        assert!(options.fleet.is_none());
+    }
+
+    #[test]
+    fn play_picks_the_opponent() {
+        let options = parse("play --vs computer --difficulty adaptive --profile sam_2").unwrap();
+
+        assert!(matches!(options.game_mode, Some(GameMode::SinglePlayer)));
+        assert_eq!(options.difficulty, Some(Difficulty::Adaptive));
+        assert_eq!(options.profile.as_deref(), Some("sam_2"));
+        assert!(parse("simulate --vs computer").is_err());
+    }
+
+    #[test]
+    fn join_and_replay_take_one_argument() {
+        let options = parse("join 127.0.0.1:7878").unwrap();
+        assert!(matches!(options.game_mode, Some(GameMode::NetworkJoin)));
+        assert_eq!(options.join_address.as_deref(), Some("127.0.0.1:7878"));
+
+        assert!(parse("join").is_err());
+        assert!(parse("join a b").is_err());
+        assert_eq!(
+            parse("replay game.record").unwrap().replay_path.as_deref(),
+            Some("game.record")
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_refused() {
+        assert!(parse("launch").is_err());
+        assert!(parse("simulate --games").is_err());
+        assert!(parse("simulate --games 0").is_err());
+        assert!(parse("simulate --p1 psychic").is_err());
+        assert!(parse("simulate --size 5").is_err());
+        assert!(parse("simulate --size 27x10").is_err());
+        assert!(parse("simulate --fleet Carrier:0").is_err());
+        assert!(parse("simulate --output results.txt").is_err());
+        assert!(parse("play --output results.csv").is_err());
+        assert!(parse("play --profile a/b").is_err());
+        assert!(parse("simulate --samples 0").is_err());
+        assert!(parse("host --port 0").is_err());
+        assert!(parse("simulate --colour blue").is_err());
+    }
+
+    #[test]
+    fn fleets_that_do_not_fit_are_refused() {
+        assert!(parse("hotseat --size 6 --fleet A:4,B:4,C:4,D:4,E:4,F:4,G:4,H:4,I:4").is_err());
+        // checked against the classic board when no size is given
+        assert!(parse("benchmark --fleet Long:11").is_err());
+        assert!(parse("hotseat --size 10x6 --fleet Long:8").is_ok());
+    }
+
+    #[test]
+    fn monte_carlo_settings_are_collected() {
+        let options = parse("simulate --samples 50 --time-budget 20").unwrap();
+
+        assert_eq!(options.monte_carlo_settings.sample_count, 50);
+        assert_eq!(
+            options.monte_carlo_settings.time_budget,
+            Some(Duration::from_millis(20))
+        );
+    }
+
+    #[test]
+    fn expert_difficulty_takes_monte_carlo_settings() {
+        let options = parse("play --vs computer --difficulty expert --samples 50").unwrap();
+        let difficulty = options
+            .difficulty
+            .map(|difficulty| difficulty.with_monte_carlo_settings(options.monte_carlo_settings));
+
+        assert_eq!(
+            difficulty,
+            Some(Difficulty::Expert(
+                MonteCarloSettings::default().set_sample_count(50)
+            ))
+        );
+    }
+
+    #[test]
+    fn board_sizes_may_be_square_or_rectangular() {
+        assert_eq!(parse_board_size("8"), Ok(BoardSize::new(8, 8)));
+        assert_eq!(parse_board_size("12X9"), Ok(BoardSize::new(12, 9)));
+    }
+}
@@ -1,19 +1,39 @@
-use crate::{GameBoard, Tile, GRID_SIZE};
+use crate::{GameBoard, Position, Tile};
 
-pub fn display_game_board(game_board: GameBoard, playing: bool) {
+pub fn display_game_board(game_board: &GameBoard, playing: bool) {
     let mut rows = Vec::new();
     for row in game_board.board.iter() {
         let row_string = build_row_display(row, playing);
         rows.push(row_string);
     }
 
-    println!("   1  2  3  4  5  6  7  8  9  10");
+    println!("{}", build_column_header(game_board.get_size().get_width()));
     for (i, row) in rows.iter().enumerate() {
         println!("{} {}", (i as u8 + 65) as char, row);
     }
 }
 
-pub fn build_row_display(row: &[Tile; GRID_SIZE as usize], playing: bool) -> String {
+// rows are labeled with letters and columns with numbers, such as C5
+pub fn get_position_label(position: Position) -> String {
+    format!(
+        "{}{}",
+        (position.get_y() as u8 + 65) as char,
+        position.get_x() + 1
+    )
+}
+
+// every tile is 3 characters wide, so the numbers are padded to line up with the tiles
+pub fn build_column_header(width: i8) -> String {
+    let mut header = String::from("  ");
+
+    for column in 1..=width {
+        header.push_str(&format!(" {:<2}", column));
+    }
+
+    header.trim_end().to_string()
+}
+
+pub fn build_row_display(row: &[Tile], playing: bool) -> String {
     let mut row_string = String::new();
 
     for tile in row.iter() {
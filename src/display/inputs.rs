@@ -3,7 +3,7 @@ use crossterm::{
     terminal,
 };
 
-use crate::utils::terminal::refresh_display;
+use crate::utils::terminal::{enable_raw_mode, refresh_display};
 
 pub struct NumberInput {
     message: String,
@@ -11,6 +11,12 @@ pub struct NumberInput {
     max: Option<i32>,
 }
 
+impl Default for NumberInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl NumberInput {
     pub fn new() -> Self {
         NumberInput {
@@ -38,37 +44,31 @@ impl NumberInput {
     pub fn ask(&self) -> i32 {
         println!("{}", self.message);
 
-        if self.max.is_none() {
-            println!("Number must be at least {}", self.min);
-        } else {
-            println!(
-                "Number must be between {} and {}",
-                self.min,
-                self.max.unwrap()
-            );
+        match self.max {
+            Some(max) => println!("Number must be between {} and {}", self.min, max),
+            None => println!("Number must be at least {}", self.min),
         }
 
         let mut current_number_string = String::new();
 
         loop {
             println!("> {}", current_number_string);
-            terminal::enable_raw_mode().expect("Failed to enable raw mode");
+            enable_raw_mode();
 
             let event = read().unwrap();
-            match event {
-                Event::Key(KeyEvent {
-                    code,
-                    kind: KeyEventKind::Press,
-                    ..
-                }) => match code {
+            if let Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) = event
+            {
+                match code {
                     KeyCode::Char('q') => {
                         terminal::disable_raw_mode().unwrap();
                         std::process::exit(0);
                     }
-                    KeyCode::Char(c) => {
-                        if c.is_numeric() {
-                            current_number_string.push(c);
-                        }
+                    KeyCode::Char(c) if c.is_numeric() => {
+                        current_number_string.push(c);
                     }
                     KeyCode::Backspace => {
                         current_number_string.pop();
@@ -88,8 +88,72 @@ impl NumberInput {
                         current_number_string = String::new();
                     }
                     _ => {}
-                },
-                _ => {}
+                }
+            }
+
+            terminal::disable_raw_mode().expect("Failed to disable raw mode");
+            refresh_display(1);
+        }
+    }
+}
+
+pub struct TextInput {
+    message: String,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        TextInput {
+            message: String::new(),
+        }
+    }
+
+    pub fn set_message(mut self, message: &str) -> Self {
+        self.message = message.to_string();
+        self
+    }
+
+    // 'q' can be part of the text, so esc is used to quit instead
+    pub fn ask(&self) -> String {
+        println!("{}", self.message);
+        println!("Press enter to confirm or esc to quit");
+
+        let mut current_text = String::new();
+
+        loop {
+            println!("> {}", current_text);
+            enable_raw_mode();
+
+            let event = read().unwrap();
+            if let Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) = event
+            {
+                match code {
+                    KeyCode::Esc => {
+                        terminal::disable_raw_mode().unwrap();
+                        std::process::exit(0);
+                    }
+                    KeyCode::Char(c) => {
+                        current_text.push(c);
+                    }
+                    KeyCode::Backspace => {
+                        current_text.pop();
+                    }
+                    KeyCode::Enter if !current_text.trim().is_empty() => {
+                        terminal::disable_raw_mode().expect("Failed to disable raw mode");
+                        return current_text;
+                    }
+                    _ => {}
+                }
             }
 
             terminal::disable_raw_mode().expect("Failed to disable raw mode");
@@ -102,6 +166,12 @@ pub struct Confirm {
     message: String,
 }
 
+impl Default for Confirm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Confirm {
     pub fn new() -> Self {
         Confirm {
@@ -119,15 +189,16 @@ impl Confirm {
         println!("Press enter to confirm or c to cancel");
 
         loop {
-            terminal::enable_raw_mode().expect("Failed to enable raw mode");
+            enable_raw_mode();
 
             let event = read().unwrap();
-            match event {
-                Event::Key(KeyEvent {
-                    code,
-                    kind: KeyEventKind::Press,
-                    ..
-                }) => match code {
+            if let Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) = event
+            {
+                match code {
                     KeyCode::Char('q') => {
                         terminal::disable_raw_mode().unwrap();
                         std::process::exit(0);
@@ -143,8 +214,7 @@ impl Confirm {
                         return true;
                     }
                     _ => {}
-                },
-                _ => {}
+                }
             }
             terminal::disable_raw_mode().expect("Failed to disable raw mode");
         }
@@ -157,6 +227,12 @@ pub struct OptionSelect {
     options: Vec<String>,
 }
 
+impl Default for OptionSelect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl OptionSelect {
     pub fn new() -> Self {
         OptionSelect {
@@ -194,15 +270,16 @@ impl OptionSelect {
                 }
                 println!("  [{}] {}", i + 1, option);
             }
-            terminal::enable_raw_mode().expect("Failed to enable raw mode");
+            enable_raw_mode();
 
             let event = read().unwrap();
-            match event {
-                Event::Key(KeyEvent {
-                    code,
-                    kind: KeyEventKind::Press,
-                    ..
-                }) => match code {
+            if let Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            }) = event
+            {
+                match code {
                     KeyCode::Char('q') => {
                         terminal::disable_raw_mode().unwrap();
                         std::process::exit(0);
@@ -226,8 +303,7 @@ impl OptionSelect {
                         return self.options[current_option].to_string();
                     }
                     _ => {}
-                },
-                _ => {}
+                }
             }
             terminal::disable_raw_mode().expect("Failed to disable raw mode");
             refresh_display(self.options.len() as u16);
@@ -0,0 +1,194 @@
+use crate::display::create_progress_bar;
+use crate::game::computer::computer_setup::computer_setup;
+use crate::game::computer::Computer;
+use crate::game::process_attack;
+use crate::game::simulation::{
+    get_game_seed, match_attack_strategy_to_computer, play_in_parallel, ComputerStats,
+};
+use crate::game::statistics::{get_bucket_width, histogram, percentile, print_histogram, Summary};
+use crate::game::ShotOutcome;
+use crate::utils::create_rng;
+use crate::{BenchmarkConfig, ComputerAttackStrategy, GameBoard};
+use rand::Rng;
+use std::ops::Range;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+const PERCENTILES: [f64; 7] = [5.0, 10.0, 25.0, 50.0, 75.0, 90.0, 95.0];
+const SHOTS_BUCKETS: usize = 10;
+
+/// How many shots a strategy needed to clear each board of a benchmark, see [`run_benchmark`].
+pub struct BenchmarkResults {
+    pub attack_strategy: ComputerAttackStrategy,
+    // in game order
+    pub shots_to_clear: Vec<u32>,
+    // the seed the boards were set up with, running again with it gives the same games
+    pub seed: u64,
+}
+
+impl BenchmarkResults {
+    pub fn new(attack_strategy: ComputerAttackStrategy, seed: u64) -> Self {
+        Self {
+            attack_strategy,
+            shots_to_clear: Vec::new(),
+            seed,
+        }
+    }
+
+    // combines the results of games played on another thread
+    pub fn merge(&mut self, other: BenchmarkResults) {
+        self.shots_to_clear.extend(other.shots_to_clear);
+    }
+
+    pub fn get_shots_to_clear(&self) -> Vec<f64> {
+        self.shots_to_clear
+            .iter()
+            .map(|shots| *shots as f64)
+            .collect()
+    }
+
+    pub fn print_results(&self) {
+        println!();
+        println!("Benchmark Results");
+        println!("-----------------");
+        println!();
+
+        println!(
+            "Attack Strategy: {}",
+            self.attack_strategy.get_attack_strategy_name()
+        );
+        println!("Boards Cleared: {}", self.shots_to_clear.len());
+        println!("Seed: {}", self.seed);
+        println!();
+
+        let shots_to_clear = self.get_shots_to_clear();
+        let summary = Summary::new(&shots_to_clear);
+
+        println!("Shots to Clear");
+        println!("--------------");
+        println!();
+        println!("{}", summary.get_summary_line());
+        println!();
+
+        for percentage in PERCENTILES {
+            println!(
+                "{}th percentile: {:.1}",
+                percentage,
+                percentile(&shots_to_clear, percentage)
+            );
+        }
+        println!();
+
+        let bucket_width = get_bucket_width(&summary, SHOTS_BUCKETS);
+        print_histogram(
+            &histogram(&shots_to_clear, summary.min, bucket_width),
+            bucket_width,
+            |start, end| format!("{} - {}", start, end - 1.0),
+        );
+        println!();
+    }
+}
+
+pub fn benchmark(benchmark_config: BenchmarkConfig) {
+    println!("Clearing Boards...");
+
+    let benchmark_results = run_benchmark(&benchmark_config, create_progress_bar);
+
+    benchmark_results.print_results();
+}
+
+/// Lets the strategy fire at a board set up by the computer until every ship is sunk, once for
+/// every game, with no opponent firing back.
+///
+/// Every board is set up from its own seed, mixed from the benchmark's seed and the board's number
+/// like the games of a simulation.
+/// `on_game_played` is called from the calling thread with the number of boards cleared so far and
+/// the number of boards to clear.
+pub fn run_benchmark(
+    benchmark_config: &BenchmarkConfig,
+    on_game_played: impl FnMut(i32, i32),
+) -> BenchmarkResults {
+    let seed = benchmark_config.seed.unwrap_or_else(rand::random);
+    let mut benchmark_results = BenchmarkResults::new(benchmark_config.attack_strategy, seed);
+
+    let worker_results = play_in_parallel(
+        benchmark_config.games_to_play,
+        on_game_played,
+        |games, games_played| clear_boards(benchmark_config, seed, games, games_played),
+    );
+
+    for worker_results in worker_results {
+        benchmark_results.merge(worker_results);
+    }
+
+    benchmark_results
+}
+
+/// Lets the computer fire at the board until every ship on it is sunk.
+pub fn clear_board(computer: &mut Computer, target_board: &mut GameBoard) -> ComputerStats {
+    let mut computer_stats = ComputerStats::default();
+
+    loop {
+        let attack_position = computer.calculate_attack(target_board);
+        let Some(shot_outcome) = process_attack(target_board, attack_position).get_shot_outcome()
+        else {
+            panic!("a computer clearing a board made an invalid attack");
+        };
+        computer.record_attack_result(attack_position, &shot_outcome);
+
+        computer_stats.shots_fired += 1;
+        match shot_outcome {
+            ShotOutcome::Miss => computer_stats.misses += 1,
+            ShotOutcome::Hit => computer_stats.hits += 1,
+            ShotOutcome::Sunk(_) => {
+                computer_stats.hits += 1;
+                computer_stats.ships_sunk += 1;
+            }
+            ShotOutcome::Won(_) => {
+                computer_stats.hits += 1;
+                computer_stats.ships_sunk += 1;
+                return computer_stats;
+            }
+        }
+    }
+}
+
+fn clear_boards(
+    benchmark_config: &BenchmarkConfig,
+    seed: u64,
+    games: Range<i32>,
+    total_games_played: &AtomicI32,
+) -> BenchmarkResults {
+    let board_size = benchmark_config.board_size;
+    let fleet = &benchmark_config.fleet;
+
+    let mut computer = match_attack_strategy_to_computer(
+        &benchmark_config.attack_strategy,
+        board_size,
+        fleet,
+        create_rng(Some(seed)),
+    )
+    .unwrap_or_else(|error| panic!("the benchmarked computer could not be set up: {}", error));
+    let mut benchmark_results = BenchmarkResults::new(benchmark_config.attack_strategy, seed);
+
+    for game_index in games {
+        let mut game_rng = create_rng(Some(get_game_seed(seed, game_index)));
+        computer.set_rng(create_rng(Some(game_rng.gen())));
+        if let Err(error) = computer.start_new_game() {
+            panic!(
+                "the benchmarked computer's fleet could not be placed: {}",
+                error
+            );
+        }
+
+        let mut target_board = computer_setup(board_size, fleet, &mut game_rng)
+            .unwrap_or_else(|error| panic!("the benchmark board could not be set up: {}", error));
+        let computer_stats = clear_board(&mut computer, &mut target_board);
+
+        benchmark_results
+            .shots_to_clear
+            .push(computer_stats.shots_fired);
+        total_games_played.fetch_add(1, Ordering::Relaxed);
+    }
+
+    benchmark_results
+}
@@ -0,0 +1,97 @@
+use crate::game::save::placement_lines;
+use crate::GameBoard;
+use sha2::{Digest, Sha256};
+
+// a promise about where the ships are, published before the first shot and checked once the
+// board is revealed, the salt keeps the layout from being guessed by hashing every possible board
+pub struct BoardCommitment {
+    salt: String,
+    hash: String,
+}
+
+impl BoardCommitment {
+    pub fn new(board: &GameBoard) -> Self {
+        let salt = to_hex(&rand::random::<[u8; 32]>());
+        let hash = commitment_hash(&salt, board);
+
+        Self { salt, hash }
+    }
+
+    pub fn get_salt(&self) -> &str {
+        &self.salt
+    }
+
+    pub fn get_hash(&self) -> &str {
+        &self.hash
+    }
+}
+
+// the placed ships are hashed in the same form they are revealed in, one line per ship
+pub fn commitment_hash(salt: &str, board: &GameBoard) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    for line in placement_lines(board) {
+        hasher.update(b"\n");
+        hasher.update(line.as_bytes());
+    }
+
+    to_hex(&hasher.finalize())
+}
+
+pub fn verify_commitment(hash: &str, salt: &str, board: &GameBoard) -> bool {
+    commitment_hash(salt, board) == hash
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::computer::computer_setup::computer_setup;
+    use crate::game::fleet::FleetDefinition;
+    use crate::utils::create_rng;
+    use crate::BoardSize;
+
+    fn board(seed: u64) -> GameBoard {
+        let fleet = FleetDefinition::classic();
+        computer_setup(BoardSize::new(10, 10), &fleet, &mut create_rng(Some(seed))).unwrap()
+    }
+
+    #[test]
+    fn commitment_verifies_the_committed_board() {
+        let board = board(1);
+        let commitment = BoardCommitment::new(&board);
+
+        assert!(verify_commitment(
+            commitment.get_hash(),
+            commitment.get_salt(),
+            &board
+        ));
+    }
+
+    #[test]
+    fn commitment_detects_tampering() {
+        let board = board(1);
+        let commitment = BoardCommitment::new(&board);
+        let other_salt = BoardCommitment::new(&board);
+
+        assert_ne!(board, self::board(2));
+        assert!(!verify_commitment(
+            commitment.get_hash(),
+            commitment.get_salt(),
+            &self::board(2)
+        ));
+        assert!(!verify_commitment(
+            commitment.get_hash(),
+            other_salt.get_salt(),
+            &board
+        ));
+        assert!(!verify_commitment(
+            other_salt.get_hash(),
+            commitment.get_salt(),
+            &board
+        ));
+    }
+}
@@ -1,66 +1,214 @@
-use crate::game::place_ship_on_board;
+use crate::game::fleet::FleetDefinition;
 
 use rand::Rng;
 
-use crate::utils::{random_50_50, ships::get_ship};
-use crate::{GameBoard, Ship, ShipType, GRID_SIZE};
+use crate::game::engine::GameError;
+use crate::utils::{create_rng, random_50_50, GameRng};
+use crate::{BoardSize, GameBoard, Position, Ship, ShipId, ShipOrientation, Tile};
 
-pub fn computer_setup() -> GameBoard {
-    let mut board = GameBoard::new();
-    let ships = make_random_list_of_ships();
+// on small boards the ships placed first can leave no room for the rest, so start over when stuck
+const MAX_PLACEMENT_ATTEMPTS: u32 = 1000;
+// fleets that are still stuck after this many fresh starts are taken not to fit at all
+const MAX_FLEET_ATTEMPTS: u32 = 200;
+// fleets a weighted placement starts over with before its rules are given up on
+const MAX_WEIGHTED_FLEET_ATTEMPTS: u32 = 100;
 
-    let mut rng = rand::thread_rng();
+/// Places every ship uniformly at random where it fits.
+///
+/// Fails with [`GameError::FleetDoesNotFit`] when the fleet could not be placed after many fresh
+/// starts, such as nine 4 long ships on a 6x6 board, which has room for their tiles but can not
+/// hold them.
+pub fn computer_setup(
+    board_size: BoardSize,
+    fleet: &FleetDefinition,
+    rng: &mut GameRng,
+) -> Result<GameBoard, GameError> {
+    if !fleet.fits_on(board_size) {
+        return Err(GameError::FleetDoesNotFit);
+    }
+
+    for _ in 0..MAX_FLEET_ATTEMPTS {
+        if let Some(board) = try_computer_setup(board_size, fleet, rng) {
+            return Ok(board);
+        }
+    }
+
+    Err(GameError::FleetDoesNotFit)
+}
+
+/// Checks that the fleet can be placed on a board of the size by placing it once, for settings
+/// picked by the player before a game starts.
+pub fn check_fleet_fits(board_size: BoardSize, fleet: &FleetDefinition) -> Result<(), GameError> {
+    computer_setup(board_size, fleet, &mut create_rng(Some(0))).map(|_| ())
+}
+
+fn try_computer_setup(
+    board_size: BoardSize,
+    fleet: &FleetDefinition,
+    rng: &mut GameRng,
+) -> Option<GameBoard> {
+    let mut board = GameBoard::new(board_size, fleet.clone());
+
+    for (fleet_index, ship_class) in fleet.ships.iter().enumerate() {
+        let ship_id = ShipId(fleet_index as u8);
 
-    for ship in ships.iter() {
         let mut placed = false;
+        let mut attempts = 0;
         while !placed {
-            let row: usize = rng.gen_range(0..GRID_SIZE as usize);
-            let col: usize = rng.gen_range(0..GRID_SIZE as usize);
+            if attempts >= MAX_PLACEMENT_ATTEMPTS {
+                return None;
+            }
+            attempts += 1;
 
-            let result = place_ship_on_board(board.board.clone(), ship, row, col, false);
+            let ship = make_randomly_oriented_ship(ship_id, ship_class.length, rng);
+            let row = rng.gen_range(0..board_size.get_height());
+            let col = rng.gen_range(0..board_size.get_width());
 
-            if result.0 {
-                board.board = result.1;
-                placed = true;
-            }
+            placed = board.place_ship(ship, Position::new(row, col)).is_ok();
         }
     }
 
-    board
+    Some(board)
 }
 
-fn make_random_list_of_ships() -> Vec<Ship> {
-    let mut ships = Vec::new();
-
-    if random_50_50() {
-        ships.push(get_ship(ShipType::CarrierHorizontal));
+fn make_randomly_oriented_ship(ship_id: ShipId, length: u8, rng: &mut GameRng) -> Ship {
+    if random_50_50(rng) {
+        Ship::new(ship_id, length, ShipOrientation::Horizontal)
     } else {
-        ships.push(get_ship(ShipType::CarrierVertical));
+        Ship::new(ship_id, length, ShipOrientation::Vertical)
     }
+}
 
-    if random_50_50() {
-        ships.push(get_ship(ShipType::BattleshipHorizontal));
-    } else {
-        ships.push(get_ship(ShipType::BattleshipVertical));
+/// Places the ships one after the other, each at one of the places it fits with a chance in
+/// proportion to `get_weight`, which is given the board with the ships placed so far and the tiles
+/// the next ship would cover. A weight of 0 rules the place out.
+///
+/// When the weights rule out every place for a ship too often, such as ships that may not touch on
+/// a crowded board, the fleet is placed uniformly at random instead.
+pub fn weighted_computer_setup(
+    board_size: BoardSize,
+    fleet: &FleetDefinition,
+    rng: &mut GameRng,
+    get_weight: impl Fn(&GameBoard, &[Position]) -> f64,
+) -> Result<GameBoard, GameError> {
+    if !fleet.fits_on(board_size) {
+        return Err(GameError::FleetDoesNotFit);
     }
 
-    if random_50_50() {
-        ships.push(get_ship(ShipType::CruiserHorizontal));
-    } else {
-        ships.push(get_ship(ShipType::CruiserVertical));
+    for _ in 0..MAX_WEIGHTED_FLEET_ATTEMPTS {
+        if let Some(board) = try_weighted_computer_setup(board_size, fleet, rng, &get_weight) {
+            return Ok(board);
+        }
     }
 
-    if random_50_50() {
-        ships.push(get_ship(ShipType::SubmarineHorizontal));
-    } else {
-        ships.push(get_ship(ShipType::SubmarineVertical));
+    computer_setup(board_size, fleet, rng)
+}
+
+fn try_weighted_computer_setup(
+    board_size: BoardSize,
+    fleet: &FleetDefinition,
+    rng: &mut GameRng,
+    get_weight: &impl Fn(&GameBoard, &[Position]) -> f64,
+) -> Option<GameBoard> {
+    let mut board = GameBoard::new(board_size, fleet.clone());
+
+    for (fleet_index, ship_class) in fleet.ships.iter().enumerate() {
+        let ship_length = ship_class.length as i8;
+        let mut placements = Vec::new();
+        let mut total_weight = 0.0;
+
+        for (orientation, y_step, x_step) in [
+            (ShipOrientation::Horizontal, 0, 1),
+            (ShipOrientation::Vertical, 1, 0),
+        ] {
+            for y in 0..board_size.get_height() - (ship_length - 1) * y_step {
+                for x in 0..board_size.get_width() - (ship_length - 1) * x_step {
+                    let cells: Vec<Position> = (0..ship_length)
+                        .map(|i| Position::new(y + i * y_step, x + i * x_step))
+                        .collect();
+
+                    if cells
+                        .iter()
+                        .any(|cell| board.get_tile_at_position(*cell) != Tile::Unknown)
+                    {
+                        continue;
+                    }
+
+                    let weight = get_weight(&board, &cells);
+                    if weight > 0.0 {
+                        total_weight += weight;
+                        placements.push((orientation, Position::new(y, x), weight));
+                    }
+                }
+            }
+        }
+
+        if placements.is_empty() {
+            return None;
+        }
+
+        let mut pick = rng.gen_range(0.0..total_weight);
+        let (orientation, position, _) = placements
+            .iter()
+            .find(|(_, _, weight)| {
+                pick -= weight;
+                pick < 0.0
+            })
+            .unwrap_or(&placements[placements.len() - 1]);
+
+        let ship = Ship::new(ShipId(fleet_index as u8), ship_class.length, *orientation);
+        board.place_ship(ship, *position).ok()?;
     }
 
-    if random_50_50() {
-        ships.push(get_ship(ShipType::DestroyerHorizontal));
-    } else {
-        ships.push(get_ship(ShipType::DestroyerVertical));
+    Some(board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computer_setup_places_every_ship() {
+        let fleet = FleetDefinition::russian();
+        let board = computer_setup(BoardSize::new(6, 6), &fleet, &mut create_rng(Some(1))).unwrap();
+
+        assert!(board.get_fleet().all_ships_placed());
+    }
+
+    #[test]
+    fn computer_setup_gives_up_on_fleets_that_can_not_be_packed() {
+        // 36 tiles of ships on a 6x6 board, which can not be tiled with 4 long ships
+        let fleet = FleetDefinition::parse("Bars", "A:4,B:4,C:4,D:4,E:4,F:4,G:4,H:4,I:4").unwrap();
+
+        assert!(fleet.fits_on(BoardSize::new(6, 6)));
+        assert_eq!(
+            check_fleet_fits(BoardSize::new(6, 6), &fleet),
+            Err(GameError::FleetDoesNotFit)
+        );
+        assert_eq!(
+            weighted_computer_setup(
+                BoardSize::new(6, 6),
+                &fleet,
+                &mut create_rng(Some(1)),
+                |_, _| { 1.0 }
+            ),
+            Err(GameError::FleetDoesNotFit)
+        );
     }
 
-    ships
+    #[test]
+    fn has_room_for_sees_both_orientations() {
+        let fleet = FleetDefinition::parse("Test", "A:6,B:6,C:6,D:6,E:6").unwrap();
+        let mut board = GameBoard::new(BoardSize::new(6, 6), fleet);
+
+        for row in 0..5 {
+            let ship = Ship::new(ShipId(row as u8), 6, ShipOrientation::Horizontal);
+            board.place_ship(ship, Position::new(row, 0)).unwrap();
+        }
+
+        assert!(board.has_room_for(6));
+        board.place_marker_on_board(Position::new(5, 3), Tile::Miss);
+        assert!(!board.has_room_for(6));
+        assert!(board.has_room_for(3));
+    }
 }
@@ -0,0 +1,117 @@
+use super::probability_density::ProbabilityDensityAttackStrategy;
+use crate::game::computer::AttackStrategy;
+use crate::game::heatmap::PlacementHeatmap;
+use crate::game::observed_board::{ObservedBoard, ObservedTile};
+use crate::utils::GameRng;
+use crate::Position;
+use rand::Rng;
+
+// how many games' worth of weight the even spread of ships starts with, so a single game does not
+// decide everything
+const PRIOR_GAMES: f64 = 2.0;
+
+/// Targets like [`ProbabilityDensityAttackStrategy`], but leans towards the tiles where the player
+/// put their ships in earlier games.
+pub struct AdaptiveAttackStrategy {
+    heatmap: PlacementHeatmap,
+}
+
+impl AdaptiveAttackStrategy {
+    pub fn new(heatmap: PlacementHeatmap) -> Self {
+        Self { heatmap }
+    }
+
+    // how much more often than an even spread would give the player has put a ship on the tile,
+    // 1 without any games
+    fn get_habit_weight(&self, enemy_view: &ObservedBoard, position: Position) -> f64 {
+        let board_size = enemy_view.get_size();
+        let ship_tiles: u32 = enemy_view
+            .get_fleet()
+            .ships
+            .iter()
+            .map(|ship| ship.length as u32)
+            .sum();
+        let even_frequency =
+            ship_tiles as f64 / (board_size.get_width() as f64 * board_size.get_height() as f64);
+
+        let games = self.heatmap.get_games() as f64;
+        let frequency = (self.heatmap.get_ship_frequency(position) * games
+            + even_frequency * PRIOR_GAMES)
+            / (games + PRIOR_GAMES);
+
+        frequency / even_frequency
+    }
+}
+
+impl AttackStrategy for AdaptiveAttackStrategy {
+    fn calculate_best_attack(&mut self, enemy_view: &ObservedBoard, rng: &mut GameRng) -> Position {
+        let density = ProbabilityDensityAttackStrategy::calculate_density(enemy_view);
+
+        let mut best_positions = Vec::new();
+        let mut highest_score = 0.0;
+        for (y, row) in density.iter().enumerate() {
+            for (x, count) in row.iter().enumerate() {
+                let position = Position::new(y as i8, x as i8);
+                if enemy_view.get_tile_at_position(position) != ObservedTile::Unknown {
+                    continue;
+                }
+
+                let score = *count * self.get_habit_weight(enemy_view, position);
+                if score > highest_score {
+                    highest_score = score;
+                    best_positions.clear();
+                }
+                if score == highest_score && score > 0.0 {
+                    best_positions.push(position);
+                }
+            }
+        }
+
+        if best_positions.is_empty() {
+            return self.get_random_position(enemy_view, rng);
+        }
+
+        best_positions[rng.gen_range(0..best_positions.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardSize, FleetDefinition, GameBoard, Ship, ShipId, ShipOrientation};
+
+    #[test]
+    fn habit_weight_moves_away_from_the_prior_as_games_are_recorded() {
+        let size = BoardSize::new(6, 6);
+        let fleet = FleetDefinition::parse("Test", "Sub:2").unwrap();
+        let enemy_view = ObservedBoard::new(size, fleet.clone());
+        // the player always puts the sub in the top left corner
+        let mut board = GameBoard::new(size, fleet);
+        let ship = Ship::new(ShipId(0), 2, ShipOrientation::Horizontal);
+        board.place_ship(ship, Position::new(0, 0)).unwrap();
+
+        let mut heatmap = PlacementHeatmap::new("alice", size);
+        let mut previous_weights = (1.0, 1.0);
+        for games in 0..5 {
+            let strategy = AdaptiveAttackStrategy::new(heatmap.clone());
+            let weights = (
+                strategy.get_habit_weight(&enemy_view, Position::new(0, 1)),
+                strategy.get_habit_weight(&enemy_view, Position::new(1, 1)),
+            );
+
+            if games == 0 {
+                assert_eq!(weights, (1.0, 1.0));
+            } else {
+                assert!(weights.0 > previous_weights.0);
+                assert!(weights.1 < previous_weights.1);
+            }
+            previous_weights = weights;
+            heatmap.record_board(&board);
+        }
+
+        // 4 games against a prior of 2 games with an even spread, where 1/18 of the tiles hold a
+        // ship
+        assert!((previous_weights.0 - (4.0 + 2.0 / 18.0) / 6.0 * 18.0).abs() < 1e-9);
+        assert!((previous_weights.1 - (2.0 / 18.0) / 6.0 * 18.0).abs() < 1e-9);
+    }
+}
@@ -1,73 +1,251 @@
 use crate::game::computer::AttackStrategy;
-use crate::game::{process_attack, GameBoard};
-use crate::{Position, Tile};
+use crate::game::observed_board::{ObservedBoard, ObservedTile};
+use crate::game::save::{format_position, parse_position};
+use crate::utils::GameRng;
+use crate::Position;
+use rand::Rng;
 
 pub struct HuntAndTargetAttackStrategy {
     previous_attack_hits: Vec<Position>,
+    last_attack: Option<Position>,
+}
+
+impl Default for HuntAndTargetAttackStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl HuntAndTargetAttackStrategy {
     pub fn new() -> Self {
         Self {
             previous_attack_hits: Vec::new(),
+            last_attack: None,
         }
     }
 
-    fn simulate_attack_result(&mut self, enemy_board: GameBoard, position: Position) -> bool {
-        let feedback = process_attack(enemy_board, position);
-
-        feedback.sunk_a_ship
-    }
+    // learns the result of the last attack from the view, and forgets hits that belong to sunk ships
+    fn update_previous_attack_hits(&mut self, enemy_view: &ObservedBoard) {
+        if let Some(last_attack) = self.last_attack {
+            if enemy_view.get_tile_at_position(last_attack) == ObservedTile::Hit
+                && !self.previous_attack_hits.contains(&last_attack)
+            {
+                self.previous_attack_hits.push(last_attack);
+            }
+        }
 
-    fn remove_hits_from_previous_attack_on_sink(
-        &mut self,
-        enemy_board: &GameBoard,
-        sunk_ship_tile: Tile,
-    ) {
         self.previous_attack_hits
-            .retain(|&position| enemy_board.get_tile_at_position(position) != sunk_ship_tile);
+            .retain(|&position| !enemy_view.is_resolved_hit(position));
     }
-}
 
-impl AttackStrategy for HuntAndTargetAttackStrategy {
-    fn calculate_best_attack(&mut self, enemy_board: &GameBoard) -> Position {
-        if !self.previous_attack_hits.is_empty() {
-            for previous_position in self.previous_attack_hits.clone() {
-                let adjacent_positions = self.get_adjacent_positions(previous_position);
-                for adjacent_position in adjacent_positions {
-                    if !adjacent_position.is_on_board() {
-                        continue;
+    // once two hits next to each other show which way a ship lies, keeps firing along that line
+    // past either end of the hits
+    fn get_line_attack(&self, enemy_view: &ObservedBoard) -> Option<Position> {
+        for hit in &self.previous_attack_hits {
+            for (y_step, x_step) in [(0, 1), (1, 0)] {
+                let next_hit = Position::new(hit.get_y() + y_step, hit.get_x() + x_step);
+                if !self.previous_attack_hits.contains(&next_hit) {
+                    continue;
+                }
+
+                for direction in [1, -1] {
+                    let mut position = *hit;
+                    while self.previous_attack_hits.contains(&position) {
+                        position = Position::new(
+                            position.get_y() + y_step * direction,
+                            position.get_x() + x_step * direction,
+                        );
                     }
 
-                    match enemy_board.get_tile_at_position(adjacent_position) {
-                        Tile::Unknown => {
-                            return adjacent_position;
-                        }
-                        Tile::Ship(ship) => {
-                            self.previous_attack_hits.push(adjacent_position);
-                            if self.simulate_attack_result(enemy_board.clone(), adjacent_position) {
-                                self.remove_hits_from_previous_attack_on_sink(
-                                    enemy_board,
-                                    Tile::Ship(ship),
-                                );
-                            }
-                            return adjacent_position;
-                        }
-                        _ => (),
+                    if position.is_on_board(enemy_view.get_size())
+                        && enemy_view.get_tile_at_position(position) == ObservedTile::Unknown
+                    {
+                        return Some(position);
                     }
                 }
             }
         }
 
-        let position = self.get_random_position(enemy_board);
+        None
+    }
 
-        match enemy_board.get_tile_at_position(position) {
-            Tile::Ship(_) => {
-                self.previous_attack_hits.push(position);
+    // every ship still afloat covers at least one tile of a diagonal pattern spaced by the length
+    // of the smallest one, so only those tiles have to be searched
+    fn get_hunt_position(&mut self, enemy_view: &ObservedBoard, rng: &mut GameRng) -> Position {
+        let spacing = enemy_view.get_smallest_remaining_ship_length() as i8;
+        if spacing <= 1 {
+            return self.get_random_position(enemy_view, rng);
+        }
+
+        let board_size = enemy_view.get_size();
+        let mut patterns = vec![Vec::new(); spacing as usize];
+        for y in 0..board_size.get_height() {
+            for x in 0..board_size.get_width() {
+                let position = Position::new(y, x);
+                if enemy_view.get_tile_at_position(position) == ObservedTile::Unknown {
+                    patterns[((x + y) % spacing) as usize].push(position);
+                }
             }
-            _ => (),
         }
 
-        return position;
+        // the pattern with the fewest tiles left is the one that has been searched the most, so
+        // the search carries on with it, also after a sink changes the spacing or a saved game is
+        // loaded
+        let fewest_tiles_left = patterns
+            .iter()
+            .map(|pattern| pattern.len())
+            .filter(|tiles_left| *tiles_left > 0)
+            .min();
+        let Some(fewest_tiles_left) = fewest_tiles_left else {
+            return self.get_random_position(enemy_view, rng);
+        };
+        let closest_patterns: Vec<&Vec<Position>> = patterns
+            .iter()
+            .filter(|pattern| pattern.len() == fewest_tiles_left)
+            .collect();
+
+        let pattern = closest_patterns[rng.gen_range(0..closest_patterns.len())];
+        pattern[rng.gen_range(0..pattern.len())]
+    }
+}
+
+impl AttackStrategy for HuntAndTargetAttackStrategy {
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn calculate_best_attack(&mut self, enemy_view: &ObservedBoard, rng: &mut GameRng) -> Position {
+        self.update_previous_attack_hits(enemy_view);
+
+        if let Some(position) = self.get_line_attack(enemy_view) {
+            self.last_attack = Some(position);
+            return position;
+        }
+
+        for previous_position in self.previous_attack_hits.clone() {
+            let adjacent_positions = self.get_adjacent_positions(previous_position);
+            for adjacent_position in adjacent_positions {
+                if !adjacent_position.is_on_board(enemy_view.get_size()) {
+                    continue;
+                }
+
+                if enemy_view.get_tile_at_position(adjacent_position) == ObservedTile::Unknown {
+                    self.last_attack = Some(adjacent_position);
+                    return adjacent_position;
+                }
+            }
+        }
+
+        let position = self.get_hunt_position(enemy_view, rng);
+        self.last_attack = Some(position);
+
+        position
+    }
+
+    // the last attack, or "-" before the first one, followed by the hits still being targeted
+    fn save_memory(&self) -> String {
+        let mut memory = vec![self.last_attack.map_or("-".to_string(), format_position)];
+        memory.extend(
+            self.previous_attack_hits
+                .iter()
+                .map(|hit| format_position(*hit)),
+        );
+
+        memory.join(" ")
+    }
+
+    fn load_memory(&mut self, memory: &str, enemy_view: &ObservedBoard) -> Result<(), String> {
+        let invalid = || format!("Invalid hunt and target memory \"{}\"", memory);
+        // positions off the board would be attacked or looked up later on
+        let parse_on_board = |position: &str| {
+            parse_position(position)
+                .filter(|position| position.is_on_board(enemy_view.get_size()))
+                .ok_or_else(invalid)
+        };
+        let mut positions = memory.split_whitespace();
+
+        self.last_attack = match positions.next() {
+            Some("-") | None => None,
+            Some(position) => Some(parse_on_board(position)?),
+        };
+        self.previous_attack_hits = positions
+            .map(parse_on_board)
+            .collect::<Result<Vec<Position>, String>>()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::ShotOutcome;
+    use crate::utils::create_rng;
+    use crate::{BoardSize, FleetDefinition};
+
+    fn enemy_view() -> ObservedBoard {
+        ObservedBoard::new(BoardSize::new(10, 10), FleetDefinition::classic())
+    }
+
+    #[test]
+    fn load_memory_restores_saved_memory() {
+        let mut strategy = HuntAndTargetAttackStrategy::new();
+        strategy.last_attack = Some(Position::new(3, 4));
+        strategy.previous_attack_hits = vec![Position::new(3, 4), Position::new(3, 5)];
+
+        let mut restored = HuntAndTargetAttackStrategy::new();
+        restored
+            .load_memory(&strategy.save_memory(), &enemy_view())
+            .unwrap();
+
+        assert_eq!(restored.last_attack, strategy.last_attack);
+        assert_eq!(restored.previous_attack_hits, strategy.previous_attack_hits);
+    }
+
+    #[test]
+    fn load_memory_rejects_positions_off_the_board() {
+        let mut strategy = HuntAndTargetAttackStrategy::new();
+
+        assert!(strategy.load_memory("10,0", &enemy_view()).is_err());
+        assert!(strategy.load_memory("- 3,4 3,10", &enemy_view()).is_err());
+        assert!(strategy.load_memory("- 3,4", &enemy_view()).is_ok());
+    }
+
+    #[test]
+    fn hunting_keeps_to_one_pattern() {
+        let mut strategy = HuntAndTargetAttackStrategy::new();
+        let mut view = enemy_view();
+        let mut rng = create_rng(Some(3));
+
+        // the smallest ship of the classic fleet is 2 long, so every other tile is searched
+        let first_attack = strategy.calculate_best_attack(&view, &mut rng);
+        view.record_attack(first_attack, &ShotOutcome::Miss);
+        let pattern = (first_attack.get_x() + first_attack.get_y()) % 2;
+
+        for _ in 1..50 {
+            let attack = strategy.calculate_best_attack(&view, &mut rng);
+            assert_eq!((attack.get_x() + attack.get_y()) % 2, pattern);
+            assert_eq!(view.get_tile_at_position(attack), ObservedTile::Unknown);
+            view.record_attack(attack, &ShotOutcome::Miss);
+        }
+    }
+
+    #[test]
+    fn two_hits_in_a_row_are_followed_along_their_line() {
+        let mut strategy = HuntAndTargetAttackStrategy::new();
+        let mut view = enemy_view();
+        let mut rng = create_rng(Some(3));
+        view.record_attack(Position::new(4, 4), &ShotOutcome::Hit);
+        view.record_attack(Position::new(4, 5), &ShotOutcome::Hit);
+        strategy.previous_attack_hits = vec![Position::new(4, 4)];
+        strategy.last_attack = Some(Position::new(4, 5));
+
+        let attack = strategy.calculate_best_attack(&view, &mut rng);
+        assert_eq!(attack, Position::new(4, 6));
+
+        // past the miss at one end, the other end of the hits is next
+        view.record_attack(attack, &ShotOutcome::Miss);
+        let attack = strategy.calculate_best_attack(&view, &mut rng);
+        assert_eq!(attack, Position::new(4, 3));
     }
 }
@@ -0,0 +1,291 @@
+use crate::game::computer::AttackStrategy;
+use crate::game::observed_board::{ObservedBoard, ObservedTile};
+use crate::utils::GameRng;
+use crate::{BoardSize, Position};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_SAMPLE_COUNT: u32 = 500;
+// layouts that fail to place every ship are thrown away, this caps how many are tried per sample
+// so a view that hardly any layout agrees with can not stall the computer
+const ATTEMPTS_PER_SAMPLE: u32 = 10;
+// random spots tried for a ship before the layout is given up on
+const ATTEMPTS_PER_SHIP: u32 = 100;
+
+/// How hard the Monte Carlo strategy thinks about each shot.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MonteCarloSettings {
+    pub sample_count: u32,
+    // stops sampling early once this much time has passed, keeping interactive games responsive.
+    // how many samples fit in the budget depends on the speed of the computer, so a seeded game
+    // with a time budget can not be replayed exactly
+    pub time_budget: Option<Duration>,
+}
+
+impl Default for MonteCarloSettings {
+    fn default() -> Self {
+        Self {
+            sample_count: DEFAULT_SAMPLE_COUNT,
+            time_budget: None,
+        }
+    }
+}
+
+impl MonteCarloSettings {
+    pub fn set_sample_count(mut self, sample_count: u32) -> Self {
+        self.sample_count = sample_count.max(1);
+        self
+    }
+
+    pub fn set_time_budget(mut self, time_budget: Duration) -> Self {
+        self.time_budget = Some(time_budget);
+        self
+    }
+}
+
+/// Samples whole layouts of the ships still afloat that agree with the view and fires at the
+/// unknown tile that has a ship on it in the most samples.
+///
+/// Unlike [`ProbabilityDensityAttackStrategy`], every ship of a sample is checked against the
+/// others, at a cost that only grows with the number of ships rather than the number of places
+/// they could be. The ships of a sample never overlap and every unresolved hit is covered, unless
+/// a sink could not be pinned to its hits, then a hit that no ship still afloat can cover is left
+/// to the sunk ship.
+///
+/// [`ProbabilityDensityAttackStrategy`]: super::probability_density::ProbabilityDensityAttackStrategy
+pub struct MonteCarloAttackStrategy {
+    settings: MonteCarloSettings,
+}
+
+impl Default for MonteCarloAttackStrategy {
+    fn default() -> Self {
+        Self::new(MonteCarloSettings::default())
+    }
+}
+
+impl MonteCarloAttackStrategy {
+    pub fn new(settings: MonteCarloSettings) -> Self {
+        Self { settings }
+    }
+
+    // a layout of the ships still afloat, as the tiles they cover, or none when the ships could
+    // not be fit around the misses and each other. ships are first placed over the unresolved
+    // hits, so samples lean towards layouts that need few ships to explain the hits
+    fn sample_layout(
+        &self,
+        enemy_view: &ObservedBoard,
+        unresolved_hits: &[Position],
+        rng: &mut GameRng,
+    ) -> Option<Vec<Vec<bool>>> {
+        let board_size = enemy_view.get_size();
+        let mut occupied =
+            vec![vec![false; board_size.get_width() as usize]; board_size.get_height() as usize];
+
+        let mut ship_lengths: Vec<i8> = enemy_view
+            .get_remaining_ships()
+            .iter()
+            .map(|ship| ship.length as i8)
+            .collect();
+        ship_lengths.shuffle(rng);
+
+        let mut hits = unresolved_hits.to_vec();
+        hits.shuffle(rng);
+
+        for hit in hits {
+            if occupied[hit.get_y() as usize][hit.get_x() as usize] {
+                continue;
+            }
+
+            let mut placements = Vec::new();
+            for (ship_index, ship_length) in ship_lengths.iter().enumerate() {
+                for (y_step, x_step) in [(0, 1), (1, 0)] {
+                    for offset in 0..*ship_length {
+                        let start = Position::new(
+                            hit.get_y() - offset * y_step,
+                            hit.get_x() - offset * x_step,
+                        );
+                        let cells = get_cells(start, *ship_length, y_step, x_step);
+                        if can_place(enemy_view, &occupied, &cells) {
+                            placements.push((ship_index, cells));
+                        }
+                    }
+                }
+            }
+
+            // a hit no ship still afloat can cover is part of a sunk ship that could not be
+            // pinned down, so it is left for the sunk ship. without a sunk ship the layout does
+            // not agree with the view
+            match placements.choose(rng) {
+                Some((ship_index, cells)) => {
+                    occupy(&mut occupied, cells);
+                    ship_lengths.remove(*ship_index);
+                }
+                None if enemy_view.sunk_ships().is_empty() => return None,
+                None => {}
+            }
+        }
+
+        for ship_length in ship_lengths {
+            let cells = (0..ATTEMPTS_PER_SHIP).find_map(|_| {
+                let cells = get_random_cells(board_size, ship_length, rng);
+                can_place(enemy_view, &occupied, &cells).then_some(cells)
+            })?;
+            occupy(&mut occupied, &cells);
+        }
+
+        Some(occupied)
+    }
+}
+
+fn get_cells(start: Position, ship_length: i8, y_step: i8, x_step: i8) -> Vec<Position> {
+    (0..ship_length)
+        .map(|i| Position::new(start.get_y() + i * y_step, start.get_x() + i * x_step))
+        .collect()
+}
+
+fn get_random_cells(board_size: BoardSize, ship_length: i8, rng: &mut GameRng) -> Vec<Position> {
+    let (y_step, x_step) = if rng.gen_bool(0.5) { (0, 1) } else { (1, 0) };
+    let y = rng.gen_range(0..=(board_size.get_height() - 1 - (ship_length - 1) * y_step).max(0));
+    let x = rng.gen_range(0..=(board_size.get_width() - 1 - (ship_length - 1) * x_step).max(0));
+
+    get_cells(Position::new(y, x), ship_length, y_step, x_step)
+}
+
+// a ship can cover unknown tiles and unresolved hits that no other ship of the layout covers
+fn can_place(enemy_view: &ObservedBoard, occupied: &[Vec<bool>], cells: &[Position]) -> bool {
+    cells.iter().all(|cell| {
+        cell.is_on_board(enemy_view.get_size())
+            && !occupied[cell.get_y() as usize][cell.get_x() as usize]
+            && match enemy_view.get_tile_at_position(*cell) {
+                ObservedTile::Unknown => true,
+                ObservedTile::Hit => !enemy_view.is_resolved_hit(*cell),
+                ObservedTile::Miss => false,
+            }
+    })
+}
+
+fn occupy(occupied: &mut [Vec<bool>], cells: &[Position]) {
+    for cell in cells {
+        occupied[cell.get_y() as usize][cell.get_x() as usize] = true;
+    }
+}
+
+impl AttackStrategy for MonteCarloAttackStrategy {
+    fn calculate_best_attack(&mut self, enemy_view: &ObservedBoard, rng: &mut GameRng) -> Position {
+        let started = Instant::now();
+        let board_size = enemy_view.get_size();
+        let unresolved_hits = enemy_view.unresolved_hits();
+
+        let mut ship_counts =
+            vec![vec![0u32; board_size.get_width() as usize]; board_size.get_height() as usize];
+        let mut samples = 0;
+
+        for _ in 0..self
+            .settings
+            .sample_count
+            .saturating_mul(ATTEMPTS_PER_SAMPLE)
+        {
+            if samples == self.settings.sample_count
+                || self
+                    .settings
+                    .time_budget
+                    .is_some_and(|time_budget| started.elapsed() >= time_budget)
+            {
+                break;
+            }
+
+            let Some(layout) = self.sample_layout(enemy_view, &unresolved_hits, rng) else {
+                continue;
+            };
+            samples += 1;
+
+            for (y, row) in layout.iter().enumerate() {
+                for (x, has_ship) in row.iter().enumerate() {
+                    if *has_ship {
+                        ship_counts[y][x] += 1;
+                    }
+                }
+            }
+        }
+
+        let mut best_positions = Vec::new();
+        let mut highest_count = 0;
+        for y in 0..board_size.get_height() {
+            for x in 0..board_size.get_width() {
+                let position = Position::new(y, x);
+                let count = ship_counts[y as usize][x as usize];
+
+                if enemy_view.get_tile_at_position(position) != ObservedTile::Unknown
+                    || count < highest_count
+                    || count == 0
+                {
+                    continue;
+                }
+
+                if count > highest_count {
+                    highest_count = count;
+                    best_positions.clear();
+                }
+                best_positions.push(position);
+            }
+        }
+
+        // no sample agreed with the view in time
+        if best_positions.is_empty() {
+            return self.get_random_position(enemy_view, rng);
+        }
+
+        best_positions[rng.gen_range(0..best_positions.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::ShotOutcome;
+    use crate::utils::create_rng;
+    use crate::FleetDefinition;
+
+    fn view(fleet: &str) -> ObservedBoard {
+        ObservedBoard::new(
+            BoardSize::new(5, 5),
+            FleetDefinition::parse("Test", fleet).unwrap(),
+        )
+    }
+
+    #[test]
+    fn fires_next_to_an_isolated_hit() {
+        let mut view = view("Boat:2,Destroyer:3");
+        view.record_attack(Position::new(2, 2), &ShotOutcome::Hit);
+        let neighbours = [
+            Position::new(1, 2),
+            Position::new(3, 2),
+            Position::new(2, 1),
+            Position::new(2, 3),
+        ];
+
+        for seed in 0..10 {
+            let mut strategy = MonteCarloAttackStrategy::new(MonteCarloSettings::default());
+            let attack = strategy.calculate_best_attack(&view, &mut create_rng(Some(seed)));
+
+            assert!(neighbours.contains(&attack), "{:?}", attack);
+        }
+    }
+
+    #[test]
+    fn samples_cover_every_hit_without_a_sunk_ship() {
+        let mut view = view("Boat:2");
+        view.record_attack(Position::new(0, 0), &ShotOutcome::Hit);
+        view.record_attack(Position::new(0, 4), &ShotOutcome::Hit);
+        let strategy = MonteCarloAttackStrategy::default();
+        let mut rng = create_rng(Some(1));
+
+        // a single boat can not cover both hits
+        for _ in 0..20 {
+            assert!(strategy
+                .sample_layout(&view, &view.unresolved_hits(), &mut rng)
+                .is_none());
+        }
+    }
+}
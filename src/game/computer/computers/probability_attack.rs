@@ -1,32 +1,39 @@
 use crate::game::computer::AttackStrategy;
-use crate::game::{process_attack, GameBoard};
-use crate::{Position, Tile, DEBUG, GRID_SIZE};
+use crate::game::observed_board::{ObservedBoard, ObservedTile, SinkAnnouncement};
+use crate::utils::GameRng;
+use crate::{Position, DEBUG};
 
 pub struct ProbabilityAttackStrategy {
-    sunk_ships: Vec<Tile>,
+    sunk_ships: Vec<SinkAnnouncement>,
     smallest_ship_length: i8,
 }
 
+impl Default for ProbabilityAttackStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ProbabilityAttackStrategy {
     pub fn new() -> Self {
         Self {
             sunk_ships: Vec::new(),
-            smallest_ship_length: 2,
+            // filled in from the enemy fleet on the first attack
+            smallest_ship_length: 0,
         }
     }
 
     fn position_is_surrounded_by_sunk_ships(
         &self,
-        enemy_board: &GameBoard,
+        enemy_view: &ObservedBoard,
         position: Position,
     ) -> bool {
         let adjacent_positions = self.get_adjacent_positions(position);
 
         for adjacent_position in adjacent_positions {
-            if adjacent_position.is_on_board() {
-                match enemy_board.get_tile_at_position(adjacent_position) {
-                    Tile::Unknown | Tile::Ship(_) => return false,
-                    _ => (),
+            if adjacent_position.is_on_board(enemy_view.get_size()) {
+                if enemy_view.get_tile_at_position(adjacent_position) == ObservedTile::Unknown {
+                    return false;
                 }
             } else {
                 return false;
@@ -36,37 +43,50 @@ impl ProbabilityAttackStrategy {
         true
     }
 
-    fn add_sunk_ship(&mut self, tile: Tile) {
-        self.sunk_ships.push(tile);
+    fn add_sunk_ship(&mut self, sunk_ship: SinkAnnouncement) {
+        self.sunk_ships.push(sunk_ship);
     }
 
-    fn update_smallest_ship_on_board(&mut self, sunk_ship: Tile) {
-        match sunk_ship {
-            Tile::Ship(ship) => {
-                if ship.get_ship_length() < self.smallest_ship_length as u8 {
-                    self.smallest_ship_length = ship.get_ship_length() as i8;
-                }
+    fn update_smallest_ship_on_board(&mut self, enemy_view: &ObservedBoard) {
+        self.smallest_ship_length = enemy_view.get_smallest_remaining_ship_length() as i8;
+    }
+
+    // picks up any sinks announced since the last attack
+    fn update_sunk_ships(&mut self, enemy_view: &ObservedBoard) {
+        if self.smallest_ship_length == 0 {
+            self.update_smallest_ship_on_board(enemy_view);
+        }
+
+        let new_sunk_ships: Vec<SinkAnnouncement> = enemy_view
+            .sunk_ships()
+            .iter()
+            .skip(self.sunk_ships.len())
+            .cloned()
+            .collect();
+
+        if !new_sunk_ships.is_empty() {
+            for sunk_ship in new_sunk_ships {
+                self.add_sunk_ship(sunk_ship);
             }
-            _ => (),
+            self.update_smallest_ship_on_board(enemy_view);
         }
     }
 
     fn calculate_probability(
         &self,
-        enemy_board: &GameBoard,
+        enemy_view: &ObservedBoard,
         position: Position,
         adjacent_positions: Vec<Position>,
     ) -> f64 {
         let mut probability = 1.0;
+        let board_size = enemy_view.get_size();
 
-        if enemy_board.get_tile_at_position(position) == Tile::Hit
-            || enemy_board.get_tile_at_position(position) == Tile::Miss
-        {
+        if enemy_view.get_tile_at_position(position) != ObservedTile::Unknown {
             return 0.0;
         }
 
         // if the position is closer to the center, increase probability by 1, up to a maximum of 5
-        let center_position = Position::new(4, 4);
+        let center_position = board_size.get_center();
 
         let distance_from_center = (position.get_x() - center_position.get_x()).abs()
             + (position.get_y() - center_position.get_y()).abs();
@@ -76,16 +96,16 @@ impl ProbabilityAttackStrategy {
         }
 
         for adjacent_position in adjacent_positions {
-            if adjacent_position.is_on_board() {
-                if let Tile::Miss = enemy_board.get_tile_at_position(adjacent_position) {
-                    probability -= 2.0;
-                } else if let Tile::Hit = enemy_board.get_tile_at_position(adjacent_position) {
-                    probability += 20.0;
+            if adjacent_position.is_on_board(enemy_view.get_size()) {
+                match enemy_view.get_tile_at_position(adjacent_position) {
+                    ObservedTile::Miss => probability -= 2.0,
+                    ObservedTile::Hit => probability += 20.0,
+                    ObservedTile::Unknown => (),
                 }
             }
         }
 
-        if position.get_x() + self.smallest_ship_length < GRID_SIZE as i8 {
+        if position.get_x() + self.smallest_ship_length < board_size.get_width() {
             probability += 1.0;
         }
 
@@ -93,7 +113,7 @@ impl ProbabilityAttackStrategy {
             probability += 1.0;
         }
 
-        if position.get_y() + self.smallest_ship_length < GRID_SIZE as i8 {
+        if position.get_y() + self.smallest_ship_length < board_size.get_height() {
             probability += 1.0;
         }
 
@@ -101,7 +121,7 @@ impl ProbabilityAttackStrategy {
             probability += 1.0;
         }
 
-        if self.position_is_surrounded_by_sunk_ships(enemy_board, position) {
+        if self.position_is_surrounded_by_sunk_ships(enemy_view, position) {
             probability -= 30.0;
         }
 
@@ -110,42 +130,48 @@ impl ProbabilityAttackStrategy {
 }
 
 impl AttackStrategy for ProbabilityAttackStrategy {
-    fn calculate_best_attack(&mut self, enemy_board: &GameBoard) -> Position {
-        let mut highest_probability_position = self.get_random_position(enemy_board);
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn calculate_best_attack(&mut self, enemy_view: &ObservedBoard, rng: &mut GameRng) -> Position {
+        self.update_sunk_ships(enemy_view);
+
+        let mut highest_probability_position = self.get_random_position(enemy_view, rng);
         let mut highest_probability = 0.0;
 
-        for x in 0..GRID_SIZE {
-            for y in 0..GRID_SIZE {
-                let position = Position::new(x as i8, y as i8);
+        let board_size = enemy_view.get_size();
+
+        for y in 0..board_size.get_height() {
+            for x in 0..board_size.get_width() {
+                let position = Position::new(y, x);
 
                 let adjacent_positions = self.get_adjacent_positions(position);
 
-                match enemy_board.get_tile_at_position(position) {
-                    Tile::Unknown | Tile::Ship(_) => {
-                        let probability =
-                            self.calculate_probability(enemy_board, position, adjacent_positions);
+                if enemy_view.get_tile_at_position(position) == ObservedTile::Unknown {
+                    let probability =
+                        self.calculate_probability(enemy_view, position, adjacent_positions);
 
-                        if probability > highest_probability {
-                            highest_probability = probability;
-                            highest_probability_position = position;
-                        }
+                    if probability > highest_probability {
+                        highest_probability = probability;
+                        highest_probability_position = position;
                     }
-                    _ => (),
                 }
             }
         }
 
-        let mut probability_grid = [[0.0; 10]; 10];
-        for x in 0..10 {
-            for y in 0..10 {
-                let position = Position::new(x as i8, y as i8);
-                let adjacent_positions = self.get_adjacent_positions(position);
-                probability_grid[x][y] =
-                    self.calculate_probability(enemy_board, position, adjacent_positions);
+        if DEBUG {
+            let mut probability_grid =
+                vec![vec![0.0; board_size.get_width() as usize]; board_size.get_height() as usize];
+            for (y, row) in probability_grid.iter_mut().enumerate() {
+                for (x, probability) in row.iter_mut().enumerate() {
+                    let position = Position::new(y as i8, x as i8);
+                    let adjacent_positions = self.get_adjacent_positions(position);
+                    *probability =
+                        self.calculate_probability(enemy_view, position, adjacent_positions);
+                }
             }
-        }
 
-        if DEBUG {
             println!("Probability grid:");
             let mut row_strings = Vec::new();
             for row in probability_grid.iter() {
@@ -160,13 +186,29 @@ impl AttackStrategy for ProbabilityAttackStrategy {
             }
         }
 
-        let simulated_attack_result = process_attack(*enemy_board, highest_probability_position);
+        highest_probability_position
+    }
+
+    // the smallest ship still afloat and how many of the announced sinks have been seen
+    fn save_memory(&self) -> String {
+        format!("{} {}", self.smallest_ship_length, self.sunk_ships.len())
+    }
+
+    fn load_memory(&mut self, memory: &str, enemy_view: &ObservedBoard) -> Result<(), String> {
+        let invalid = || format!("Invalid probability attack memory \"{}\"", memory);
+        let (smallest_ship_length, sunk_ships) = memory.split_once(' ').ok_or_else(invalid)?;
+        let smallest_ship_length = smallest_ship_length.parse::<i8>().map_err(|_| invalid())?;
+        let sunk_ships = sunk_ships.parse::<usize>().map_err(|_| invalid())?;
 
-        if simulated_attack_result.sunk_a_ship {
-            self.add_sunk_ship(simulated_attack_result.tile_at_attack);
-            self.update_smallest_ship_on_board(simulated_attack_result.tile_at_attack);
+        // the announcements themselves are already part of the restored view
+        let announced_sinks = enemy_view.sunk_ships();
+        if sunk_ships > announced_sinks.len() {
+            return Err(invalid());
         }
 
-        highest_probability_position
+        self.smallest_ship_length = smallest_ship_length;
+        self.sunk_ships = announced_sinks[..sunk_ships].to_vec();
+
+        Ok(())
     }
 }
@@ -0,0 +1,356 @@
+use crate::game::computer::AttackStrategy;
+use crate::game::observed_board::{ObservedBoard, ObservedTile};
+use crate::utils::GameRng;
+use crate::{Position, DEBUG};
+use rand::Rng;
+
+/// Fires at the tile with the highest density, see
+/// [`ProbabilityDensityAttackStrategy::calculate_density`].
+pub struct ProbabilityDensityAttackStrategy;
+
+impl ProbabilityDensityAttackStrategy {
+    /// For every unknown tile, how many layouts of the ships still afloat that agree with every
+    /// hit, miss and sink seen so far put a ship on it. Tiles that have been attacked are 0.
+    ///
+    /// The ships covering the hits that are not known to be part of a sunk ship are placed
+    /// together: they do not overlap each other, and every such hit is covered. Every other ship
+    /// is then counted on its own in the space left around them, so those ships are not checked
+    /// against each other. Without such hits, every ship is counted on its own.
+    ///
+    /// When a sink could not be pinned to its hits, some of them may belong to the sunk ship. If no
+    /// layout covers every hit, the layouts that leave the fewest hits to the sunk ships are
+    /// counted instead.
+    pub fn calculate_density(enemy_view: &ObservedBoard) -> Vec<Vec<f64>> {
+        let board_size = enemy_view.get_size();
+        let ship_placements: Vec<Vec<Vec<Position>>> = enemy_view
+            .get_remaining_ships()
+            .iter()
+            .map(|ship| get_legal_placements(enemy_view, ship.length as i8))
+            .collect();
+
+        let mut density_count = DensityCount {
+            enemy_view,
+            unresolved_hits: enemy_view.unresolved_hits(),
+            placed_ships: vec![None; ship_placements.len()],
+            ship_placements,
+            occupied: vec![
+                vec![false; board_size.get_width() as usize];
+                board_size.get_height() as usize
+            ],
+            density: vec![
+                vec![0.0; board_size.get_width() as usize];
+                board_size.get_height() as usize
+            ],
+        };
+        // a hit can only be part of a sunk ship when a sink could not be pinned to its hits
+        let most_hits_left_to_sunk_ships = if enemy_view.sunk_ships().is_empty() {
+            0
+        } else {
+            density_count.unresolved_hits.len()
+        };
+        for hits_left_to_sunk_ships in 0..=most_hits_left_to_sunk_ships {
+            density_count.place_over_hits(0, hits_left_to_sunk_ships);
+            if density_count
+                .density
+                .iter()
+                .flatten()
+                .any(|count| *count > 0.0)
+            {
+                break;
+            }
+        }
+
+        density_count.density
+    }
+}
+
+// every place a ship of the length could be without crossing a miss or a hit of a sunk ship
+fn get_legal_placements(enemy_view: &ObservedBoard, ship_length: i8) -> Vec<Vec<Position>> {
+    let board_size = enemy_view.get_size();
+    let mut placements = Vec::new();
+
+    for (y_step, x_step) in [(0, 1), (1, 0)] {
+        for y in 0..board_size.get_height() - (ship_length - 1) * y_step {
+            for x in 0..board_size.get_width() - (ship_length - 1) * x_step {
+                let cells: Vec<Position> = (0..ship_length)
+                    .map(|i| Position::new(y + i * y_step, x + i * x_step))
+                    .collect();
+
+                let legal = cells
+                    .iter()
+                    .all(|cell| match enemy_view.get_tile_at_position(*cell) {
+                        ObservedTile::Unknown => true,
+                        ObservedTile::Miss => false,
+                        ObservedTile::Hit => !enemy_view.is_resolved_hit(*cell),
+                    });
+                // a ship of length 1 lies the same way in both directions
+                if legal && (ship_length > 1 || y_step == 0) {
+                    placements.push(cells);
+                }
+            }
+        }
+    }
+
+    placements
+}
+
+struct DensityCount<'a> {
+    enemy_view: &'a ObservedBoard,
+    unresolved_hits: Vec<Position>,
+    // the legal placements of every ship still afloat
+    ship_placements: Vec<Vec<Vec<Position>>>,
+    // the placement each ship has in the layout being built, none for the ships counted on their own
+    placed_ships: Vec<Option<usize>>,
+    occupied: Vec<Vec<bool>>,
+    density: Vec<Vec<f64>>,
+}
+
+impl DensityCount<'_> {
+    // every layout is built once, the hits are covered in order and only one ship can cover a hit
+    // in a layout, a hit that is left to the sunk ships is blocked for the ships still afloat
+    fn place_over_hits(&mut self, hit_index: usize, hits_left_to_sunk_ships: usize) {
+        let Some(hit) = self.unresolved_hits.get(hit_index).copied() else {
+            if hits_left_to_sunk_ships == 0 {
+                self.count_layout();
+            }
+            return;
+        };
+
+        if self.is_occupied(hit) {
+            self.place_over_hits(hit_index + 1, hits_left_to_sunk_ships);
+            return;
+        }
+
+        if hits_left_to_sunk_ships > 0 {
+            self.set_occupied_at(hit, true);
+            self.place_over_hits(hit_index + 1, hits_left_to_sunk_ships - 1);
+            self.set_occupied_at(hit, false);
+        }
+
+        for ship in 0..self.ship_placements.len() {
+            if self.placed_ships[ship].is_some() {
+                continue;
+            }
+
+            for placement in 0..self.ship_placements[ship].len() {
+                let cells = &self.ship_placements[ship][placement];
+                if !cells.contains(&hit) || cells.iter().any(|cell| self.is_occupied(*cell)) {
+                    continue;
+                }
+
+                self.set_occupied(ship, placement, true);
+                self.placed_ships[ship] = Some(placement);
+                self.place_over_hits(hit_index + 1, hits_left_to_sunk_ships);
+                self.placed_ships[ship] = None;
+                self.set_occupied(ship, placement, false);
+            }
+        }
+    }
+
+    // adds a layout of the ships over the hits, with every way the other ships fit around it
+    fn count_layout(&mut self) {
+        let free_placements: Vec<(usize, Vec<&Vec<Position>>)> = (0..self.ship_placements.len())
+            .filter(|ship| self.placed_ships[*ship].is_none())
+            .map(|ship| {
+                let placements = self.ship_placements[ship]
+                    .iter()
+                    .filter(|cells| cells.iter().all(|cell| !self.is_occupied(*cell)))
+                    .collect();
+                (ship, placements)
+            })
+            .collect();
+
+        let layouts: f64 = free_placements
+            .iter()
+            .map(|(_, placements)| placements.len() as f64)
+            .product();
+        if layouts == 0.0 {
+            return;
+        }
+
+        let mut additions: Vec<(Position, f64)> = Vec::new();
+        for (ship, placement) in self.placed_ships.iter().enumerate() {
+            if let Some(placement) = placement {
+                for cell in &self.ship_placements[ship][*placement] {
+                    additions.push((*cell, layouts));
+                }
+            }
+        }
+        for (ship, placements) in &free_placements {
+            // the layouts each placement of this ship is part of
+            let layouts_per_placement: f64 = free_placements
+                .iter()
+                .filter(|(other_ship, _)| other_ship != ship)
+                .map(|(_, placements)| placements.len() as f64)
+                .product();
+
+            for cells in placements {
+                for cell in cells.iter() {
+                    additions.push((*cell, layouts_per_placement));
+                }
+            }
+        }
+
+        for (cell, count) in additions {
+            if self.enemy_view.get_tile_at_position(cell) == ObservedTile::Unknown {
+                self.density[cell.get_y() as usize][cell.get_x() as usize] += count;
+            }
+        }
+    }
+
+    fn is_occupied(&self, position: Position) -> bool {
+        self.occupied[position.get_y() as usize][position.get_x() as usize]
+    }
+
+    fn set_occupied(&mut self, ship: usize, placement: usize, occupied: bool) {
+        for index in 0..self.ship_placements[ship][placement].len() {
+            let cell = self.ship_placements[ship][placement][index];
+            self.set_occupied_at(cell, occupied);
+        }
+    }
+
+    fn set_occupied_at(&mut self, position: Position, occupied: bool) {
+        self.occupied[position.get_y() as usize][position.get_x() as usize] = occupied;
+    }
+}
+
+impl AttackStrategy for ProbabilityDensityAttackStrategy {
+    fn calculate_best_attack(&mut self, enemy_view: &ObservedBoard, rng: &mut GameRng) -> Position {
+        let density = Self::calculate_density(enemy_view);
+
+        if DEBUG {
+            println!("Probability density:");
+            for row in density.iter() {
+                let row_string: Vec<String> =
+                    row.iter().map(|count| format!("{:>10.0}", count)).collect();
+                println!("{}", row_string.join(""));
+            }
+        }
+
+        let highest_density = density.iter().flatten().copied().fold(0.0, f64::max);
+
+        // only happens when the view can not be explained by any layout, such as when a sink
+        // could not be pinned to its hits
+        if highest_density == 0.0 {
+            return self.get_random_position(enemy_view, rng);
+        }
+
+        let mut best_positions = Vec::new();
+        for (y, row) in density.iter().enumerate() {
+            for (x, count) in row.iter().enumerate() {
+                if *count == highest_density {
+                    best_positions.push(Position::new(y as i8, x as i8));
+                }
+            }
+        }
+
+        // ties are broken at random, so the computer can not be read by where it fires first
+        best_positions[rng.gen_range(0..best_positions.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::fleet::{FleetDefinition, ShipClass};
+    use crate::game::ShotOutcome;
+    use crate::BoardSize;
+
+    fn view(fleet: &str) -> ObservedBoard {
+        ObservedBoard::new(
+            BoardSize::new(3, 3),
+            FleetDefinition::parse("Test", fleet).unwrap(),
+        )
+    }
+
+    #[test]
+    fn empty_board_counts_every_placement() {
+        let density = ProbabilityDensityAttackStrategy::calculate_density(&view("Boat:2"));
+
+        assert_eq!(
+            density,
+            vec![
+                vec![2.0, 3.0, 2.0],
+                vec![3.0, 4.0, 3.0],
+                vec![2.0, 3.0, 2.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn single_ship_must_cover_the_hit() {
+        let mut view = view("Boat:2");
+        view.record_attack(Position::new(1, 1), &ShotOutcome::Hit);
+
+        let density = ProbabilityDensityAttackStrategy::calculate_density(&view);
+
+        assert_eq!(
+            density,
+            vec![
+                vec![0.0, 1.0, 0.0],
+                vec![1.0, 0.0, 1.0],
+                vec![0.0, 1.0, 0.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn other_ships_are_counted_around_the_ship_on_the_hit() {
+        let mut view = view("Boat:2,Sub:2");
+        view.record_attack(Position::new(0, 0), &ShotOutcome::Hit);
+
+        let density = ProbabilityDensityAttackStrategy::calculate_density(&view);
+
+        // either ship lies on the hit in one of 2 ways, with 8 places left for the other
+        assert_eq!(
+            density,
+            vec![
+                vec![0.0, 20.0, 6.0],
+                vec![20.0, 12.0, 12.0],
+                vec![6.0, 12.0, 8.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn hits_are_covered_together() {
+        let mut cruiser_view = view("Cruiser:3");
+        let mut boat_view = view("Boat:2");
+        for view in [&mut cruiser_view, &mut boat_view] {
+            view.record_attack(Position::new(0, 0), &ShotOutcome::Hit);
+            view.record_attack(Position::new(0, 2), &ShotOutcome::Hit);
+        }
+
+        let cruiser_density = ProbabilityDensityAttackStrategy::calculate_density(&cruiser_view);
+        let boat_density = ProbabilityDensityAttackStrategy::calculate_density(&boat_view);
+
+        assert_eq!(cruiser_density[0][1], 1.0);
+        assert_eq!(cruiser_density.iter().flatten().sum::<f64>(), 1.0);
+        // a single boat can not cover both hits
+        assert!(boat_density.iter().flatten().all(|count| *count == 0.0));
+    }
+
+    #[test]
+    fn hits_of_an_ambiguous_sink_can_be_left_to_the_sunk_ship() {
+        let mut view = view("Destroyer:2,Boat:2");
+        // the destroyer sunk at the corner of the L lies along one of its arms
+        view.record_attack(Position::new(1, 0), &ShotOutcome::Hit);
+        view.record_attack(Position::new(0, 1), &ShotOutcome::Hit);
+        view.record_attack(
+            Position::new(1, 1),
+            &ShotOutcome::Sunk(ShipClass::new("Destroyer", 2)),
+        );
+
+        let density = ProbabilityDensityAttackStrategy::calculate_density(&view);
+
+        // the boat can not cover both hits, so it covers either one
+        assert_eq!(
+            density,
+            vec![
+                vec![2.0, 0.0, 1.0],
+                vec![0.0, 0.0, 0.0],
+                vec![1.0, 0.0, 0.0]
+            ]
+        );
+    }
+}
@@ -1,11 +1,12 @@
 use crate::game::computer::AttackStrategy;
-use crate::game::GameBoard;
+use crate::game::observed_board::ObservedBoard;
+use crate::utils::GameRng;
 use crate::Position;
 
 pub struct RandomAttackStrategy;
 
 impl AttackStrategy for RandomAttackStrategy {
-    fn calculate_best_attack(&mut self, enemy_board: &GameBoard) -> Position {
-        self.get_random_position(enemy_board)
+    fn calculate_best_attack(&mut self, enemy_view: &ObservedBoard, rng: &mut GameRng) -> Position {
+        self.get_random_position(enemy_view, rng)
     }
 }
@@ -1,53 +1,219 @@
 pub mod computer_setup;
 
 pub mod computers {
+    pub mod adaptive;
+    pub mod hacker;
     pub mod hunt_and_target;
+    pub mod monte_carlo;
     pub mod probability_attack;
+    pub mod probability_density;
     pub mod random_attack;
-    pub mod hacker;
 }
 
-use crate::game::GameBoard;
-use crate::{Position, Tile, GRID_SIZE};
+pub mod placements {
+    pub mod anti_density;
+    pub mod edge_heavy;
+    pub mod no_touching;
+    pub mod spread_out;
+    pub mod uniform;
+}
+
+use crate::game::engine::GameError;
+use crate::game::fleet::FleetDefinition;
+use crate::game::observed_board::{ObservedBoard, ObservedTile};
+use crate::game::{GameBoard, ShotOutcome};
+use crate::utils::{create_rng, GameRng};
+use crate::{BoardSize, Position};
 use computer_setup::computer_setup;
+use placements::uniform::UniformPlacement;
+use rand::Rng;
+
+/// What a computer is allowed to see when it picks a tile to attack.
+pub enum ComputerTargeting {
+    // only sees what the attacks have revealed so far
+    Observed(Box<dyn AttackStrategy>),
+    // sees the real enemy board, hidden ships included
+    Omniscient(Box<dyn OmniscientAttackStrategy>),
+}
 
+/// A computer player, with its own board and its view of the enemy board.
 pub struct Computer {
     pub computer_board: GameBoard,
-    pub attack_strategy: Box<dyn AttackStrategy>,
+    pub attack_strategy: ComputerTargeting,
+    pub placement_strategy: Box<dyn PlacementStrategy>,
+    pub enemy_view: ObservedBoard,
+    board_size: BoardSize,
+    fleet: FleetDefinition,
+    rng: GameRng,
 }
 
 impl Computer {
-    pub fn new(attack_strategy: Box<dyn AttackStrategy>) -> Self {
-        let computer_board = computer_setup();
-        Self {
+    /// Fails when the fleet does not fit on the board, see [`computer_setup()`].
+    pub fn new(
+        attack_strategy: Box<dyn AttackStrategy>,
+        board_size: BoardSize,
+        fleet: FleetDefinition,
+        rng: GameRng,
+    ) -> Result<Self, GameError> {
+        Self::with_targeting(
+            ComputerTargeting::Observed(attack_strategy),
+            board_size,
+            fleet,
+            rng,
+        )
+    }
+
+    pub fn new_omniscient(
+        attack_strategy: Box<dyn OmniscientAttackStrategy>,
+        board_size: BoardSize,
+        fleet: FleetDefinition,
+        rng: GameRng,
+    ) -> Result<Self, GameError> {
+        Self::with_targeting(
+            ComputerTargeting::Omniscient(attack_strategy),
+            board_size,
+            fleet,
+            rng,
+        )
+    }
+
+    fn with_targeting(
+        attack_strategy: ComputerTargeting,
+        board_size: BoardSize,
+        fleet: FleetDefinition,
+        mut rng: GameRng,
+    ) -> Result<Self, GameError> {
+        let computer_board = computer_setup(board_size, &fleet, &mut rng)?;
+        Ok(Self {
             computer_board,
             attack_strategy,
+            placement_strategy: Box::new(UniformPlacement),
+            enemy_view: ObservedBoard::new(board_size, fleet.clone()),
+            board_size,
+            fleet,
+            rng,
+        })
+    }
+
+    /// Picks a saved game back up, the board is the one the enemy has been attacking.
+    ///
+    /// The state of the random number generator is not saved, so it starts from a fresh seed.
+    pub fn restore(
+        attack_strategy: Box<dyn AttackStrategy>,
+        computer_board: GameBoard,
+        enemy_view: ObservedBoard,
+    ) -> Self {
+        let board_size = computer_board.get_size();
+        let fleet = computer_board.get_fleet_definition().clone();
+
+        Self {
+            computer_board,
+            attack_strategy: ComputerTargeting::Observed(attack_strategy),
+            placement_strategy: Box::new(UniformPlacement),
+            enemy_view,
+            board_size,
+            fleet,
+            rng: create_rng(None),
+        }
+    }
+
+    /// Replaces the random number generator, such as with one seeded for the next game.
+    pub fn set_rng(&mut self, rng: GameRng) {
+        self.rng = rng;
+    }
+
+    /// Places the ships with the strategy from now on, starting with a new board right away.
+    pub fn set_placement_strategy(
+        &mut self,
+        placement_strategy: Box<dyn PlacementStrategy>,
+    ) -> Result<(), GameError> {
+        self.placement_strategy = placement_strategy;
+        self.computer_board =
+            self.placement_strategy
+                .place_fleet(self.board_size, &self.fleet, &mut self.rng)?;
+
+        Ok(())
+    }
+
+    /// Sets up a fresh board and forgets everything learned about the last enemy board, so the
+    /// next game starts as if the computer was new.
+    pub fn start_new_game(&mut self) -> Result<(), GameError> {
+        self.computer_board =
+            self.placement_strategy
+                .place_fleet(self.board_size, &self.fleet, &mut self.rng)?;
+        self.enemy_view = ObservedBoard::new(self.board_size, self.fleet.clone());
+
+        match &mut self.attack_strategy {
+            ComputerTargeting::Observed(strategy) => strategy.reset(),
+            ComputerTargeting::Omniscient(strategy) => strategy.reset(),
+        }
+
+        Ok(())
+    }
+
+    /// The full enemy board is only handed to omniscient strategies.
+    pub fn calculate_attack(&mut self, enemy_board: &GameBoard) -> Position {
+        match &mut self.attack_strategy {
+            ComputerTargeting::Observed(strategy) => {
+                strategy.calculate_best_attack(&self.enemy_view, &mut self.rng)
+            }
+            ComputerTargeting::Omniscient(strategy) => {
+                strategy.calculate_best_attack_with_full_board(enemy_board)
+            }
+        }
+    }
+
+    pub fn record_attack_result(&mut self, attack_position: Position, outcome: &ShotOutcome) {
+        self.enemy_view.record_attack(attack_position, outcome);
+    }
+
+    pub fn save_memory(&self) -> String {
+        match &self.attack_strategy {
+            ComputerTargeting::Observed(strategy) => strategy.save_memory(),
+            ComputerTargeting::Omniscient(_) => String::new(),
         }
     }
 
-    pub fn regenerate_computer_board(&mut self) {
-        self.computer_board = computer_setup();
+    pub fn load_memory(&mut self, memory: &str) -> Result<(), String> {
+        match &mut self.attack_strategy {
+            ComputerTargeting::Observed(strategy) => strategy.load_memory(memory, &self.enemy_view),
+            ComputerTargeting::Omniscient(_) => Ok(()),
+        }
     }
 }
 
+/// Picks the next tile to attack from what the attacks so far have revealed.
+///
+/// Implement this to add a computer player, only `calculate_best_attack` is required.
 pub trait AttackStrategy {
-    fn calculate_best_attack(&mut self, enemy_board: &GameBoard) -> Position;
+    fn calculate_best_attack(&mut self, enemy_view: &ObservedBoard, rng: &mut GameRng) -> Position;
 
-    fn generate_random_position(&self) -> Position {
-        let x = rand::random::<usize>() % GRID_SIZE as usize;
-        let y = rand::random::<usize>() % GRID_SIZE as usize;
+    /// Called before every new game, strategies that remember anything between attacks have to
+    /// forget it here.
+    fn reset(&mut self) {}
 
-        Position::new(x as i8, y as i8)
+    /// What the strategy remembers between attacks, as a single line for save files.
+    fn save_memory(&self) -> String {
+        String::new()
     }
 
-    fn get_random_position(&mut self, enemy_board: &GameBoard) -> Position {
-        let mut position = self.generate_random_position();
+    fn load_memory(&mut self, _memory: &str, _enemy_view: &ObservedBoard) -> Result<(), String> {
+        Ok(())
+    }
 
-        loop {
-            match enemy_board.get_tile_at_position(position) {
-                Tile::Unknown | Tile::Ship(_) => break,
-                _ => position = self.generate_random_position(),
-            }
+    fn generate_random_position(&self, board_size: BoardSize, rng: &mut GameRng) -> Position {
+        let x = rng.gen_range(0..board_size.get_width());
+        let y = rng.gen_range(0..board_size.get_height());
+
+        Position::new(y, x)
+    }
+
+    fn get_random_position(&mut self, enemy_view: &ObservedBoard, rng: &mut GameRng) -> Position {
+        let board_size = enemy_view.get_size();
+        let mut position = self.generate_random_position(board_size, rng);
+
+        while enemy_view.get_tile_at_position(position) != ObservedTile::Unknown {
+            position = self.generate_random_position(board_size, rng);
         }
 
         position
@@ -62,3 +228,24 @@ pub trait AttackStrategy {
         ]
     }
 }
+
+/// Picks where the computer puts its ships at the start of a game.
+///
+/// Most strategies only weigh the places a ship fits, see
+/// [`computer_setup::weighted_computer_setup`].
+pub trait PlacementStrategy {
+    fn place_fleet(
+        &mut self,
+        board_size: BoardSize,
+        fleet: &FleetDefinition,
+        rng: &mut GameRng,
+    ) -> Result<GameBoard, GameError>;
+}
+
+/// Kept separate from [`AttackStrategy`] so that cheating is always an explicit choice.
+pub trait OmniscientAttackStrategy {
+    fn calculate_best_attack_with_full_board(&mut self, enemy_board: &GameBoard) -> Position;
+
+    /// Called before every new game, see [`AttackStrategy::reset`].
+    fn reset(&mut self) {}
+}
@@ -0,0 +1,92 @@
+use crate::game::computer::computer_setup::weighted_computer_setup;
+use crate::game::computer::computers::probability_density::ProbabilityDensityAttackStrategy;
+use crate::game::computer::PlacementStrategy;
+use crate::game::engine::GameError;
+use crate::game::fleet::FleetDefinition;
+use crate::game::observed_board::ObservedBoard;
+use crate::utils::GameRng;
+use crate::{BoardSize, GameBoard};
+
+// how sharply places covered by the most placements are avoided. a higher power holds out a little
+// longer against density searches, but packs the fleet into the corners and along the edges,
+// where a search that starts there finds it sooner
+const DENSITY_POWER: i32 = 3;
+
+/// Avoids the tiles a probability density targeter fires at first on an empty board, which are
+/// the tiles the most placements of the fleet cover.
+pub struct AntiDensityPlacement;
+
+impl PlacementStrategy for AntiDensityPlacement {
+    fn place_fleet(
+        &mut self,
+        board_size: BoardSize,
+        fleet: &FleetDefinition,
+        rng: &mut GameRng,
+    ) -> Result<GameBoard, GameError> {
+        let density = ProbabilityDensityAttackStrategy::calculate_density(&ObservedBoard::new(
+            board_size,
+            fleet.clone(),
+        ));
+        let highest_density = density.iter().flatten().copied().fold(1.0, f64::max);
+
+        weighted_computer_setup(board_size, fleet, rng, |_, cells| {
+            // the density of the place compared to the densest tile, from near 0 up to 1
+            let mean_density = cells
+                .iter()
+                .map(|cell| density[cell.get_y() as usize][cell.get_x() as usize])
+                .sum::<f64>()
+                / cells.len() as f64
+                / highest_density;
+
+            1.0 / mean_density.max(f64::EPSILON).powi(DENSITY_POWER)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::computer::placements::uniform::UniformPlacement;
+    use crate::utils::create_rng;
+    use crate::Tile;
+
+    // the summed empty board density of the ship tiles, over boards of many seeds
+    fn sum_ship_density(strategy: &mut impl PlacementStrategy) -> f64 {
+        let board_size = BoardSize::new(10, 10);
+        let fleet = FleetDefinition::classic();
+        let density = ProbabilityDensityAttackStrategy::calculate_density(&ObservedBoard::new(
+            board_size,
+            fleet.clone(),
+        ));
+        let mut ship_density = 0.0;
+
+        for seed in 0..50 {
+            let board = strategy
+                .place_fleet(board_size, &fleet, &mut create_rng(Some(seed)))
+                .unwrap();
+
+            for (y, row) in board.board.iter().enumerate() {
+                for (x, tile) in row.iter().enumerate() {
+                    if matches!(tile, Tile::Ship(_)) {
+                        ship_density += density[y][x];
+                    }
+                }
+            }
+        }
+
+        ship_density
+    }
+
+    #[test]
+    fn ships_lie_on_fewer_placements_than_with_uniform_placements() {
+        let anti_density = sum_ship_density(&mut AntiDensityPlacement);
+        let uniform = sum_ship_density(&mut UniformPlacement);
+
+        assert!(
+            anti_density < uniform * 0.9,
+            "{} against {}",
+            anti_density,
+            uniform
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use crate::game::computer::computer_setup::weighted_computer_setup;
+use crate::game::computer::PlacementStrategy;
+use crate::game::engine::GameError;
+use crate::game::fleet::FleetDefinition;
+use crate::utils::GameRng;
+use crate::{BoardSize, GameBoard};
+
+// how much more likely a place is for every tile it has on the outer ring of the board
+const EDGE_TILE_WEIGHT: f64 = 2.0;
+
+/// Prefers places along the edges of the board, which searches that start in the middle reach last.
+pub struct EdgeHeavyPlacement;
+
+impl PlacementStrategy for EdgeHeavyPlacement {
+    fn place_fleet(
+        &mut self,
+        board_size: BoardSize,
+        fleet: &FleetDefinition,
+        rng: &mut GameRng,
+    ) -> Result<GameBoard, GameError> {
+        weighted_computer_setup(board_size, fleet, rng, |_, cells| {
+            let edge_tiles = cells
+                .iter()
+                .filter(|cell| {
+                    cell.get_y() == 0
+                        || cell.get_x() == 0
+                        || cell.get_y() == board_size.get_height() - 1
+                        || cell.get_x() == board_size.get_width() - 1
+                })
+                .count();
+
+            EDGE_TILE_WEIGHT.powi(edge_tiles as i32)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::computer::placements::uniform::UniformPlacement;
+    use crate::utils::create_rng;
+    use crate::Tile;
+
+    // the ship tiles on the outer ring of the board, over boards of many seeds
+    fn count_edge_tiles(strategy: &mut impl PlacementStrategy) -> usize {
+        let board_size = BoardSize::new(10, 10);
+        let mut edge_tiles = 0;
+
+        for seed in 0..50 {
+            let board = strategy
+                .place_fleet(
+                    board_size,
+                    &FleetDefinition::classic(),
+                    &mut create_rng(Some(seed)),
+                )
+                .unwrap();
+
+            for (y, row) in board.board.iter().enumerate() {
+                for (x, tile) in row.iter().enumerate() {
+                    let on_edge = y == 0 || x == 0 || y == 9 || x == 9;
+                    if on_edge && matches!(tile, Tile::Ship(_)) {
+                        edge_tiles += 1;
+                    }
+                }
+            }
+        }
+
+        edge_tiles
+    }
+
+    #[test]
+    fn more_ships_lie_on_the_edges_than_with_uniform_placements() {
+        let edge_heavy_tiles = count_edge_tiles(&mut EdgeHeavyPlacement);
+        let uniform_tiles = count_edge_tiles(&mut UniformPlacement);
+
+        assert!(
+            edge_heavy_tiles > uniform_tiles * 5 / 4,
+            "{} against {}",
+            edge_heavy_tiles,
+            uniform_tiles
+        );
+    }
+}
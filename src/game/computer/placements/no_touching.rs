@@ -0,0 +1,95 @@
+use crate::game::computer::computer_setup::weighted_computer_setup;
+use crate::game::computer::PlacementStrategy;
+use crate::game::engine::GameError;
+use crate::game::fleet::FleetDefinition;
+use crate::utils::GameRng;
+use crate::{BoardSize, GameBoard, Position, Tile};
+
+/// Keeps a free tile between ships, diagonals included, so finding one ship tells nothing about
+/// the tiles right next to it.
+///
+/// A fleet that can not be kept apart, such as a large fleet on a small board, is placed uniformly
+/// at random by [`weighted_computer_setup`] instead, so its ships may touch.
+pub struct NoTouchingPlacement;
+
+impl PlacementStrategy for NoTouchingPlacement {
+    fn place_fleet(
+        &mut self,
+        board_size: BoardSize,
+        fleet: &FleetDefinition,
+        rng: &mut GameRng,
+    ) -> Result<GameBoard, GameError> {
+        weighted_computer_setup(board_size, fleet, rng, |board, cells| {
+            if cells.iter().any(|cell| touches_ship(board, *cell)) {
+                0.0
+            } else {
+                1.0
+            }
+        })
+    }
+}
+
+fn touches_ship(board: &GameBoard, position: Position) -> bool {
+    (-1..=1).any(|y_offset| {
+        (-1..=1).any(|x_offset| {
+            let neighbour = Position::new(position.get_y() + y_offset, position.get_x() + x_offset);
+
+            neighbour.is_on_board(board.get_size())
+                && matches!(board.get_tile_at_position(neighbour), Tile::Ship(_))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_rng;
+
+    #[test]
+    fn ships_never_touch() {
+        for seed in 0..20 {
+            let board = NoTouchingPlacement
+                .place_fleet(
+                    BoardSize::new(10, 10),
+                    &FleetDefinition::classic(),
+                    &mut create_rng(Some(seed)),
+                )
+                .unwrap();
+
+            for (y, row) in board.board.iter().enumerate() {
+                for (x, tile) in row.iter().enumerate() {
+                    let Tile::Ship(ship) = tile else {
+                        continue;
+                    };
+
+                    for y_offset in -1..=1 {
+                        for x_offset in -1..=1 {
+                            let neighbour = Position::new(y as i8 + y_offset, x as i8 + x_offset);
+                            if !neighbour.is_on_board(board.get_size()) {
+                                continue;
+                            }
+
+                            if let Tile::Ship(other_ship) = board.get_tile_at_position(neighbour) {
+                                assert_eq!(other_ship.get_id(), ship.get_id());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn crowded_fleets_are_still_placed() {
+        // 20 tiles of ships can not be kept apart on a 6x6 board
+        let board = NoTouchingPlacement
+            .place_fleet(
+                BoardSize::new(6, 6),
+                &FleetDefinition::russian(),
+                &mut create_rng(Some(1)),
+            )
+            .unwrap();
+
+        assert!(board.get_fleet().all_ships_placed());
+    }
+}
@@ -0,0 +1,54 @@
+use crate::game::computer::computer_setup::weighted_computer_setup;
+use crate::game::computer::PlacementStrategy;
+use crate::game::engine::GameError;
+use crate::game::fleet::FleetDefinition;
+use crate::utils::GameRng;
+use crate::{BoardSize, GameBoard, Position, Tile};
+
+/// Prefers places far from the ships already placed, so the fleet covers the whole board and one
+/// find does not lead to the next.
+pub struct SpreadOutPlacement;
+
+impl PlacementStrategy for SpreadOutPlacement {
+    fn place_fleet(
+        &mut self,
+        board_size: BoardSize,
+        fleet: &FleetDefinition,
+        rng: &mut GameRng,
+    ) -> Result<GameBoard, GameError> {
+        weighted_computer_setup(board_size, fleet, rng, |board, cells| {
+            let ship_tiles = get_ship_tiles(board);
+            if ship_tiles.is_empty() {
+                return 1.0;
+            }
+
+            // the distance from the closest tile of another ship, squared to favour the far places
+            let distance = cells
+                .iter()
+                .flat_map(|cell| {
+                    ship_tiles.iter().map(move |ship_tile| {
+                        (cell.get_y() - ship_tile.get_y()).abs()
+                            + (cell.get_x() - ship_tile.get_x()).abs()
+                    })
+                })
+                .min()
+                .unwrap_or(0);
+
+            (distance as f64).powi(2)
+        })
+    }
+}
+
+fn get_ship_tiles(board: &GameBoard) -> Vec<Position> {
+    let mut ship_tiles = Vec::new();
+
+    for (y, row) in board.board.iter().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
+            if matches!(tile, Tile::Ship(_)) {
+                ship_tiles.push(Position::new(y as i8, x as i8));
+            }
+        }
+    }
+
+    ship_tiles
+}
@@ -0,0 +1,19 @@
+use crate::game::computer::computer_setup::computer_setup;
+use crate::game::computer::PlacementStrategy;
+use crate::game::engine::GameError;
+use crate::game::fleet::FleetDefinition;
+use crate::utils::GameRng;
+use crate::{BoardSize, GameBoard};
+
+pub struct UniformPlacement;
+
+impl PlacementStrategy for UniformPlacement {
+    fn place_fleet(
+        &mut self,
+        board_size: BoardSize,
+        fleet: &FleetDefinition,
+        rng: &mut GameRng,
+    ) -> Result<GameBoard, GameError> {
+        computer_setup(board_size, fleet, rng)
+    }
+}
@@ -0,0 +1,403 @@
+use crate::game::fleet::FleetDefinition;
+use crate::game::{process_attack, ShotOutcome};
+use crate::{BoardSize, GameBoard, Player, Position, Ship, Tile};
+use std::fmt;
+
+/// Why a move was refused, the game is left untouched when one is returned.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GameError {
+    ShipNotInFleet,
+    ShipAlreadyPlaced,
+    ShipOffBoard,
+    ShipsOverlap,
+    // the ships can not all be packed onto the board together
+    FleetDoesNotFit,
+    // the board handed to the game is for another size or fleet, or has been attacked already
+    BoardDoesNotMatch,
+    FleetNotPlaced,
+    PlacementFinished,
+    GameAlreadyStarted,
+    GameNotStarted,
+    GameOver,
+    NotYourTurn,
+    OffBoard,
+    AlreadyAttacked,
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            GameError::ShipNotInFleet => "That ship is not part of the fleet",
+            GameError::ShipAlreadyPlaced => "That ship has already been placed",
+            GameError::ShipOffBoard => "The ship does not fit on the board there",
+            GameError::ShipsOverlap => "The ship would overlap another ship",
+            GameError::FleetDoesNotFit => "The fleet does not fit on the board",
+            GameError::BoardDoesNotMatch => "The board does not match the game",
+            GameError::FleetNotPlaced => "Not every ship of the fleet has been placed",
+            GameError::PlacementFinished => "Ships can not be moved once the game has started",
+            GameError::GameAlreadyStarted => {
+                "The first turn can not change once the game has started"
+            }
+            GameError::GameNotStarted => "Both fleets have to be placed before firing",
+            GameError::GameOver => "The game is already over",
+            GameError::NotYourTurn => "It is not your turn",
+            GameError::OffBoard => "That position is not on the board",
+            GameError::AlreadyAttacked => "That position has already been attacked",
+        };
+
+        write!(formatter, "{}", message)
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// Ships are placed until both fleets are complete, then shots are fired until one fleet is sunk.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GamePhase {
+    Placement,
+    Playing,
+    Finished,
+}
+
+/// The rules of a game between two players, without any terminal input or output.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Game {
+    player_one_board: GameBoard,
+    player_two_board: GameBoard,
+    current_turn: Player,
+    winner: Option<Player>,
+}
+
+impl Game {
+    /// Starts with two empty boards, player one fires first.
+    pub fn new(board_size: BoardSize, fleet: FleetDefinition) -> Self {
+        Self {
+            player_one_board: GameBoard::new(board_size, fleet.clone()),
+            player_two_board: GameBoard::new(board_size, fleet),
+            current_turn: Player::PlayerOne,
+            winner: None,
+        }
+    }
+
+    /// Picks up a game that was already being played, such as a saved one.
+    pub fn resume(
+        player_one_board: GameBoard,
+        player_two_board: GameBoard,
+        current_turn: Player,
+    ) -> Result<Self, GameError> {
+        if player_one_board.get_size() != player_two_board.get_size()
+            || player_one_board.get_fleet_definition() != player_two_board.get_fleet_definition()
+        {
+            return Err(GameError::BoardDoesNotMatch);
+        }
+
+        if !player_one_board.get_fleet().all_ships_placed()
+            || !player_two_board.get_fleet().all_ships_placed()
+        {
+            return Err(GameError::FleetNotPlaced);
+        }
+
+        let winner = if player_two_board.get_fleet().all_ships_sunk() {
+            Some(Player::PlayerOne)
+        } else if player_one_board.get_fleet().all_ships_sunk() {
+            Some(Player::PlayerTwo)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            player_one_board,
+            player_two_board,
+            current_turn,
+            winner,
+        })
+    }
+
+    pub fn get_phase(&self) -> GamePhase {
+        if self.winner.is_some() {
+            GamePhase::Finished
+        } else if self.player_one_board.get_fleet().all_ships_placed()
+            && self.player_two_board.get_fleet().all_ships_placed()
+        {
+            GamePhase::Playing
+        } else {
+            GamePhase::Placement
+        }
+    }
+
+    pub fn get_board_size(&self) -> BoardSize {
+        self.player_one_board.get_size()
+    }
+
+    pub fn get_fleet_definition(&self) -> &FleetDefinition {
+        self.player_one_board.get_fleet_definition()
+    }
+
+    /// The board of the player, with their own ships on it.
+    pub fn get_board(&self, player: Player) -> &GameBoard {
+        match player {
+            Player::PlayerOne => &self.player_one_board,
+            Player::PlayerTwo => &self.player_two_board,
+        }
+    }
+
+    fn get_board_mut(&mut self, player: Player) -> &mut GameBoard {
+        match player {
+            Player::PlayerOne => &mut self.player_one_board,
+            Player::PlayerTwo => &mut self.player_two_board,
+        }
+    }
+
+    /// The player that fires next, or the one that fired the winning shot once the game is over.
+    pub fn current_turn(&self) -> Player {
+        self.current_turn
+    }
+
+    /// The player that sunk the whole enemy fleet, if one has yet.
+    pub fn winner(&self) -> Option<Player> {
+        self.winner
+    }
+
+    /// Picks who fires first, only allowed before the first shot.
+    pub fn set_first_turn(&mut self, player: Player) -> Result<(), GameError> {
+        let shots_fired = [Player::PlayerOne, Player::PlayerTwo].iter().any(|player| {
+            self.get_board(*player)
+                .board
+                .iter()
+                .flatten()
+                .any(|tile| matches!(tile, Tile::Hit | Tile::Miss))
+        });
+
+        if shots_fired || self.get_phase() == GamePhase::Finished {
+            return Err(GameError::GameAlreadyStarted);
+        }
+
+        self.current_turn = player;
+
+        Ok(())
+    }
+
+    /// Places one ship of the player's fleet, only allowed before the game has started.
+    pub fn place_ship(
+        &mut self,
+        player: Player,
+        ship: Ship,
+        position: Position,
+    ) -> Result<(), GameError> {
+        if self.get_phase() != GamePhase::Placement {
+            return Err(GameError::PlacementFinished);
+        }
+
+        self.get_board_mut(player).place_ship(ship, position)
+    }
+
+    /// Hands the game a board that already has every ship placed, such as one set up by the
+    /// computer.
+    pub fn place_fleet(&mut self, player: Player, board: GameBoard) -> Result<(), GameError> {
+        if self.get_phase() != GamePhase::Placement {
+            return Err(GameError::PlacementFinished);
+        }
+
+        let unattacked = board
+            .board
+            .iter()
+            .flatten()
+            .all(|tile| !matches!(tile, Tile::Hit | Tile::Miss));
+
+        if board.get_size() != self.get_board_size()
+            || board.get_fleet_definition() != self.get_fleet_definition()
+            || !unattacked
+        {
+            return Err(GameError::BoardDoesNotMatch);
+        }
+
+        if !board.get_fleet().all_ships_placed() {
+            return Err(GameError::FleetNotPlaced);
+        }
+
+        *self.get_board_mut(player) = board;
+
+        Ok(())
+    }
+
+    /// Fires a shot at the other player's board, the turn passes to the other player after every
+    /// shot unless it won the game.
+    pub fn fire(&mut self, player: Player, position: Position) -> Result<ShotOutcome, GameError> {
+        match self.get_phase() {
+            GamePhase::Placement => return Err(GameError::GameNotStarted),
+            GamePhase::Finished => return Err(GameError::GameOver),
+            GamePhase::Playing => {}
+        }
+
+        if player != self.current_turn {
+            return Err(GameError::NotYourTurn);
+        }
+
+        let defender_board = self.get_board_mut(player.get_other_player());
+        if !position.is_on_board(defender_board.get_size()) {
+            return Err(GameError::OffBoard);
+        }
+
+        let Some(outcome) = process_attack(defender_board, position).get_shot_outcome() else {
+            return Err(GameError::AlreadyAttacked);
+        };
+
+        if matches!(outcome, ShotOutcome::Won(_)) {
+            self.winner = Some(player);
+        } else {
+            self.current_turn = player.get_other_player();
+        }
+
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ShipId, ShipOrientation};
+
+    // a single two tile ship on a 4x4 board, in the top left corner of both boards
+    fn started_game() -> Game {
+        let mut game = Game::new(
+            BoardSize::new(4, 4),
+            FleetDefinition::parse("Test", "Sub:2").unwrap(),
+        );
+        for player in [Player::PlayerOne, Player::PlayerTwo] {
+            let ship = Ship::new(ShipId(0), 2, ShipOrientation::Horizontal);
+            game.place_ship(player, ship, Position::new(0, 0)).unwrap();
+        }
+
+        game
+    }
+
+    #[test]
+    fn game_starts_once_both_fleets_are_placed() {
+        let mut game = Game::new(
+            BoardSize::new(4, 4),
+            FleetDefinition::parse("Test", "Sub:2").unwrap(),
+        );
+        assert_eq!(game.get_phase(), GamePhase::Placement);
+        assert_eq!(
+            game.fire(Player::PlayerOne, Position::new(0, 0)),
+            Err(GameError::GameNotStarted)
+        );
+
+        let ship = Ship::new(ShipId(0), 2, ShipOrientation::Horizontal);
+        assert_eq!(
+            game.place_ship(Player::PlayerOne, ship, Position::new(0, 3)),
+            Err(GameError::ShipOffBoard)
+        );
+
+        let game = started_game();
+        assert_eq!(game.get_phase(), GamePhase::Playing);
+        assert_eq!(game.current_turn(), Player::PlayerOne);
+    }
+
+    #[test]
+    fn placement_is_closed_once_the_game_has_started() {
+        let mut game = started_game();
+        let ship = Ship::new(ShipId(0), 2, ShipOrientation::Vertical);
+
+        assert_eq!(
+            game.place_ship(Player::PlayerOne, ship, Position::new(2, 2)),
+            Err(GameError::PlacementFinished)
+        );
+    }
+
+    #[test]
+    fn place_fleet_rejects_boards_for_another_game() {
+        let mut game = Game::new(
+            BoardSize::new(4, 4),
+            FleetDefinition::parse("Test", "Sub:2").unwrap(),
+        );
+        let other_size = GameBoard::new(
+            BoardSize::new(5, 4),
+            FleetDefinition::parse("Test", "Sub:2").unwrap(),
+        );
+        let unplaced = GameBoard::new(
+            BoardSize::new(4, 4),
+            FleetDefinition::parse("Test", "Sub:2").unwrap(),
+        );
+
+        assert_eq!(
+            game.place_fleet(Player::PlayerOne, other_size),
+            Err(GameError::BoardDoesNotMatch)
+        );
+        assert_eq!(
+            game.place_fleet(Player::PlayerOne, unplaced),
+            Err(GameError::FleetNotPlaced)
+        );
+    }
+
+    #[test]
+    fn shots_alternate_and_refused_shots_change_nothing() {
+        let mut game = started_game();
+
+        assert_eq!(
+            game.fire(Player::PlayerTwo, Position::new(0, 0)),
+            Err(GameError::NotYourTurn)
+        );
+        assert_eq!(
+            game.fire(Player::PlayerOne, Position::new(4, 0)),
+            Err(GameError::OffBoard)
+        );
+        assert_eq!(
+            game.fire(Player::PlayerOne, Position::new(3, 3)),
+            Ok(ShotOutcome::Miss)
+        );
+        assert_eq!(game.current_turn(), Player::PlayerTwo);
+
+        assert_eq!(
+            game.fire(Player::PlayerTwo, Position::new(0, 0)),
+            Ok(ShotOutcome::Hit)
+        );
+        let before = game.clone();
+        assert_eq!(
+            game.fire(Player::PlayerOne, Position::new(3, 3)),
+            Err(GameError::AlreadyAttacked)
+        );
+        assert_eq!(game, before);
+        assert_eq!(
+            game.set_first_turn(Player::PlayerTwo),
+            Err(GameError::GameAlreadyStarted)
+        );
+    }
+
+    #[test]
+    fn sinking_the_last_ship_wins_the_game() {
+        let mut game = started_game();
+
+        game.fire(Player::PlayerOne, Position::new(0, 0)).unwrap();
+        game.fire(Player::PlayerTwo, Position::new(3, 3)).unwrap();
+        let outcome = game.fire(Player::PlayerOne, Position::new(0, 1)).unwrap();
+
+        assert!(matches!(outcome, ShotOutcome::Won(_)));
+        assert_eq!(game.winner(), Some(Player::PlayerOne));
+        assert_eq!(game.current_turn(), Player::PlayerOne);
+        assert_eq!(game.get_phase(), GamePhase::Finished);
+        assert_eq!(
+            game.fire(Player::PlayerTwo, Position::new(0, 1)),
+            Err(GameError::GameOver)
+        );
+
+        let resumed = Game::resume(
+            game.get_board(Player::PlayerOne).clone(),
+            game.get_board(Player::PlayerTwo).clone(),
+            Player::PlayerTwo,
+        )
+        .unwrap();
+        assert_eq!(resumed.winner(), Some(Player::PlayerOne));
+    }
+
+    #[test]
+    fn set_first_turn_works_before_the_first_shot() {
+        let mut game = started_game();
+
+        assert_eq!(game.set_first_turn(Player::PlayerTwo), Ok(()));
+        assert_eq!(
+            game.fire(Player::PlayerTwo, Position::new(0, 0)),
+            Ok(ShotOutcome::Hit)
+        );
+    }
+}
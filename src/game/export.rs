@@ -0,0 +1,439 @@
+use crate::game::simulation::{get_game_seed, ComputerPlayer, SimulationResults};
+use crate::game::statistics::Summary;
+use crate::SimulationConfig;
+use std::fs;
+
+const COMPUTERS: [ComputerPlayer; 2] = [ComputerPlayer::ComputerOne, ComputerPlayer::ComputerTwo];
+
+/// The file formats simulation results can be exported to, picked from the file extension.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn from_path(path: &str) -> Option<Self> {
+        let (_, extension) = path.rsplit_once('.')?;
+
+        match extension.to_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+// a single exported field, kept typed so json can tell numbers from text
+enum ExportValue {
+    Text(String),
+    Integer(u64),
+    Number(f64),
+    // such as the shots to win of a computer that never won
+    Missing,
+}
+
+impl ExportValue {
+    fn to_csv(&self) -> String {
+        match self {
+            ExportValue::Text(text) => escape_csv(text),
+            ExportValue::Integer(integer) => integer.to_string(),
+            ExportValue::Number(number) => number.to_string(),
+            ExportValue::Missing => String::new(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        match self {
+            ExportValue::Text(text) => escape_json(text),
+            ExportValue::Integer(integer) => integer.to_string(),
+            ExportValue::Number(number) => number.to_string(),
+            ExportValue::Missing => "null".to_string(),
+        }
+    }
+}
+
+type ExportFields = Vec<(String, ExportValue)>;
+
+// only writes when an export was asked for, and tells the player where the results went
+pub fn write_simulation_export(
+    output_path: Option<&str>,
+    simulation_config: &SimulationConfig,
+    simulation_results: &SimulationResults,
+) {
+    if let Some(path) = output_path {
+        match export_simulation_results(path, simulation_config, simulation_results) {
+            Ok(()) => println!("Simulation results written to {}", path),
+            Err(error) => println!("{}", error),
+        }
+    }
+}
+
+/// Writes a summary of the simulation and one row per game to a .csv or .json file.
+///
+/// The csv file starts with the summary as `# name,value` comment lines, followed by a header and
+/// the games. The json file is an object with a `summary` object and a `games` array.
+pub fn export_simulation_results(
+    path: &str,
+    simulation_config: &SimulationConfig,
+    simulation_results: &SimulationResults,
+) -> Result<(), String> {
+    let format = ExportFormat::from_path(path)
+        .ok_or_else(|| format!("Unknown output format for {}, use .csv or .json", path))?;
+
+    let summary = summary_fields(simulation_config, simulation_results);
+    let games = game_rows(simulation_results);
+
+    let contents = match format {
+        ExportFormat::Csv => to_csv(&summary, &games),
+        ExportFormat::Json => to_json(&summary, &games),
+    };
+
+    fs::write(path, contents).map_err(|error| format!("Could not write {}: {}", path, error))
+}
+
+fn summary_fields(
+    simulation_config: &SimulationConfig,
+    simulation_results: &SimulationResults,
+) -> ExportFields {
+    let mut fields = vec![
+        field(
+            "computer_one_strategy",
+            text(
+                simulation_results
+                    .computer_one_attack_strategy
+                    .get_attack_strategy_name(),
+            ),
+        ),
+        field(
+            "computer_two_strategy",
+            text(
+                simulation_results
+                    .computer_two_attack_strategy
+                    .get_attack_strategy_name(),
+            ),
+        ),
+        field(
+            "board_width",
+            integer(simulation_config.board_size.get_width()),
+        ),
+        field(
+            "board_height",
+            integer(simulation_config.board_size.get_height()),
+        ),
+        field("fleet", text(simulation_config.fleet.name.clone())),
+        field("seed", ExportValue::Integer(simulation_results.seed)),
+        field("games_played", integer(simulation_results.games_played)),
+        field(
+            "computer_one_wins",
+            integer(simulation_results.computer_one_wins),
+        ),
+        field(
+            "computer_two_wins",
+            integer(simulation_results.computer_two_wins),
+        ),
+    ];
+
+    for computer in COMPUTERS {
+        let name = computer.get_export_name();
+        let wins = simulation_results
+            .stats
+            .iter()
+            .filter(|result| result.winner == computer)
+            .count();
+        let win_rate = if simulation_results.stats.is_empty() {
+            0.0
+        } else {
+            wins as f64 / simulation_results.stats.len() as f64
+        };
+        let (low, high) = simulation_results.get_win_rate_interval(computer);
+
+        fields.push(field(
+            &format!("{}_win_rate", name),
+            ExportValue::Number(win_rate),
+        ));
+        fields.push(field(
+            &format!("{}_win_rate_low", name),
+            ExportValue::Number(low),
+        ));
+        fields.push(field(
+            &format!("{}_win_rate_high", name),
+            ExportValue::Number(high),
+        ));
+
+        fields.extend(summary_statistics(
+            &format!("{}_shots_to_win", name),
+            &simulation_results.get_shots_to_win(computer),
+        ));
+        fields.extend(summary_statistics(
+            &format!("{}_hit_rate", name),
+            &simulation_results.get_hit_rates(computer),
+        ));
+    }
+
+    fields.extend(summary_statistics(
+        "game_length",
+        &simulation_results.get_game_lengths(),
+    ));
+
+    fields
+}
+
+fn summary_statistics(prefix: &str, values: &[f64]) -> ExportFields {
+    let summary = Summary::new(values);
+    let number = |value| {
+        if values.is_empty() {
+            ExportValue::Missing
+        } else {
+            ExportValue::Number(value)
+        }
+    };
+
+    vec![
+        field(&format!("{}_mean", prefix), number(summary.mean)),
+        field(&format!("{}_median", prefix), number(summary.median)),
+        field(
+            &format!("{}_standard_deviation", prefix),
+            number(summary.standard_deviation),
+        ),
+        field(&format!("{}_min", prefix), number(summary.min)),
+        field(&format!("{}_max", prefix), number(summary.max)),
+    ]
+}
+
+fn game_rows(simulation_results: &SimulationResults) -> Vec<ExportFields> {
+    simulation_results
+        .stats
+        .iter()
+        .enumerate()
+        .map(|(game_index, result)| {
+            let mut row = vec![
+                field("game", integer(game_index)),
+                field(
+                    "seed",
+                    ExportValue::Integer(get_game_seed(simulation_results.seed, game_index as i32)),
+                ),
+                field(
+                    "starting_computer",
+                    text(result.starting_computer.get_export_name()),
+                ),
+                field("winner", text(result.winner.get_export_name())),
+                field("game_length", integer(result.get_game_length())),
+            ];
+
+            for computer in COMPUTERS {
+                let name = computer.get_export_name();
+                let strategy = match computer {
+                    ComputerPlayer::ComputerOne => simulation_results.computer_one_attack_strategy,
+                    ComputerPlayer::ComputerTwo => simulation_results.computer_two_attack_strategy,
+                };
+                let stats = result.get_computer_stats(computer);
+
+                row.push(field(
+                    &format!("{}_strategy", name),
+                    text(strategy.get_attack_strategy_name()),
+                ));
+                row.push(field(&format!("{}_hits", name), integer(stats.hits)));
+                row.push(field(&format!("{}_misses", name), integer(stats.misses)));
+                row.push(field(
+                    &format!("{}_ships_sunk", name),
+                    integer(stats.ships_sunk),
+                ));
+                row.push(field(
+                    &format!("{}_shots_fired", name),
+                    integer(stats.shots_fired),
+                ));
+                row.push(field(
+                    &format!("{}_hit_rate", name),
+                    ExportValue::Number(stats.get_hit_rate()),
+                ));
+            }
+
+            row
+        })
+        .collect()
+}
+
+fn to_csv(summary: &ExportFields, games: &[ExportFields]) -> String {
+    // a comment can not span lines, so line breaks in the summary become spaces
+    let mut lines: Vec<String> = summary
+        .iter()
+        .map(|(name, value)| format!("# {},{}", name, value.to_csv().replace(['\r', '\n'], " ")))
+        .collect();
+
+    if let Some(first_game) = games.first() {
+        lines.push(
+            first_game
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+
+    for game in games {
+        lines.push(
+            game.iter()
+                .map(|(_, value)| value.to_csv())
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn to_json(summary: &ExportFields, games: &[ExportFields]) -> String {
+    let games: Vec<String> = games
+        .iter()
+        .map(|game| format!("    {}", json_object(game, "")))
+        .collect();
+
+    format!(
+        "{{\n  \"summary\": {},\n  \"games\": [\n{}\n  ]\n}}\n",
+        json_object(summary, "  "),
+        games.join(",\n")
+    )
+}
+
+// the summary is spread over lines, a game is kept on one line
+fn json_object(fields: &ExportFields, indent: &str) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{}: {}", escape_json(name), value.to_json()))
+        .collect();
+
+    if indent.is_empty() {
+        format!("{{{}}}", fields.join(", "))
+    } else {
+        format!(
+            "{{\n{indent}  {}\n{indent}}}",
+            fields.join(&format!(",\n{}  ", indent)),
+            indent = indent
+        )
+    }
+}
+
+fn escape_csv(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            character if character.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", character as u32))
+            }
+            character => escaped.push(character),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+fn field(name: &str, value: ExportValue) -> (String, ExportValue) {
+    (name.to_string(), value)
+}
+
+fn text(text: impl Into<String>) -> ExportValue {
+    ExportValue::Text(text.into())
+}
+
+// every exported count is zero or more
+fn integer(integer: impl TryInto<u64>) -> ExportValue {
+    ExportValue::Integer(integer.try_into().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a strategy name with every character that needs escaping
+    const AWKWARD_NAME: &str = "Hunt, \"Target\"\nv2";
+
+    fn fields() -> (ExportFields, Vec<ExportFields>) {
+        let summary = vec![
+            field("strategy", text(AWKWARD_NAME)),
+            field("win_rate", ExportValue::Number(0.5)),
+            field("shots_mean", ExportValue::Missing),
+        ];
+        let games = vec![
+            vec![
+                field("game", integer(0)),
+                field("strategy", text(AWKWARD_NAME)),
+            ],
+            vec![field("game", integer(1)), field("strategy", text("Random"))],
+        ];
+
+        (summary, games)
+    }
+
+    #[test]
+    fn csv_values_are_quoted_when_needed() {
+        assert_eq!(escape_csv("Random"), "Random");
+        assert_eq!(escape_csv("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(escape_json("Random"), "\"Random\"");
+        assert_eq!(
+            escape_json("a \"b\" \\ c\nd\u{1}"),
+            "\"a \\\"b\\\" \\\\ c\\nd\\u0001\""
+        );
+    }
+
+    #[test]
+    fn csv_has_summary_comments_a_header_and_a_row_per_game() {
+        let (summary, games) = fields();
+
+        assert_eq!(
+            to_csv(&summary, &games),
+            "# strategy,\"Hunt, \"\"Target\"\" v2\"\n\
+             # win_rate,0.5\n\
+             # shots_mean,\n\
+             game,strategy\n\
+             0,\"Hunt, \"\"Target\"\"\nv2\"\n\
+             1,Random\n"
+        );
+    }
+
+    #[test]
+    fn json_has_a_summary_object_and_a_games_array() {
+        let (summary, games) = fields();
+
+        assert_eq!(
+            to_json(&summary, &games),
+            "{\n  \"summary\": {\n    \"strategy\": \"Hunt, \\\"Target\\\"\\nv2\",\n    \
+             \"win_rate\": 0.5,\n    \"shots_mean\": null\n  },\n  \"games\": [\n    \
+             {\"game\": 0, \"strategy\": \"Hunt, \\\"Target\\\"\\nv2\"},\n    \
+             {\"game\": 1, \"strategy\": \"Random\"}\n  ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn format_is_picked_from_the_extension() {
+        assert_eq!(
+            ExportFormat::from_path("results.CSV"),
+            Some(ExportFormat::Csv)
+        );
+        assert_eq!(
+            ExportFormat::from_path("out.json"),
+            Some(ExportFormat::Json)
+        );
+        assert_eq!(ExportFormat::from_path("results.txt"), None);
+        assert_eq!(ExportFormat::from_path("results"), None);
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{BoardSize, Position, Ship, ShipId};
+
+/// A kind of ship in a fleet, such as a 5 long Carrier.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ShipClass {
+    pub name: String,
+    pub length: u8,
+}
+
+impl ShipClass {
+    pub fn new(name: &str, length: u8) -> Self {
+        Self {
+            name: name.to_string(),
+            length,
+        }
+    }
+}
+
+/// The ships both players place before the game starts.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FleetDefinition {
+    pub name: String,
+    pub ships: Vec<ShipClass>,
+}
+
+impl Default for FleetDefinition {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl FleetDefinition {
+    pub fn new(name: &str, ships: Vec<ShipClass>) -> Self {
+        Self {
+            name: name.to_string(),
+            ships,
+        }
+    }
+
+    pub fn classic() -> Self {
+        Self::new(
+            "Classic",
+            vec![
+                ShipClass::new("Carrier", 5),
+                ShipClass::new("Battleship", 4),
+                ShipClass::new("Cruiser", 3),
+                ShipClass::new("Submarine", 3),
+                ShipClass::new("Destroyer", 2),
+            ],
+        )
+    }
+
+    pub fn hasbro_1990() -> Self {
+        Self::new(
+            "Hasbro 1990",
+            vec![
+                ShipClass::new("Carrier", 5),
+                ShipClass::new("Battleship", 4),
+                ShipClass::new("Destroyer", 3),
+                ShipClass::new("Submarine", 3),
+                ShipClass::new("Patrol Boat", 2),
+            ],
+        )
+    }
+
+    pub fn russian() -> Self {
+        Self::new(
+            "Russian",
+            vec![
+                ShipClass::new("Battleship", 4),
+                ShipClass::new("Cruiser", 3),
+                ShipClass::new("Cruiser", 3),
+                ShipClass::new("Destroyer", 2),
+                ShipClass::new("Destroyer", 2),
+                ShipClass::new("Destroyer", 2),
+                ShipClass::new("Submarine", 1),
+                ShipClass::new("Submarine", 1),
+                ShipClass::new("Submarine", 1),
+                ShipClass::new("Submarine", 1),
+            ],
+        )
+    }
+
+    // parses a comma or newline separated list of ships, such as "Carrier:5, Battleship:4"
+    pub fn parse(name: &str, fleet_spec: &str) -> Result<Self, String> {
+        let mut ships = Vec::new();
+
+        for entry in fleet_spec.split([',', '\n']) {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+
+            let (ship_name, length) = entry
+                .rsplit_once(':')
+                .ok_or_else(|| format!("Expected name:length but found \"{}\"", entry))?;
+
+            let length = length
+                .trim()
+                .parse::<u8>()
+                .map_err(|_| format!("Invalid ship length in \"{}\"", entry))?;
+
+            if length == 0 {
+                return Err(format!("Ship length must be at least 1 in \"{}\"", entry));
+            }
+
+            ships.push(ShipClass::new(ship_name.trim(), length));
+        }
+
+        if ships.is_empty() {
+            return Err("A fleet needs at least one ship".to_string());
+        }
+
+        Ok(Self::new(name, ships))
+    }
+
+    pub fn get_ship_class(&self, fleet_index: u8) -> &ShipClass {
+        &self.ships[fleet_index as usize]
+    }
+
+    // ships that share a name are numbered so they can be told apart in menus
+    pub fn get_ship_label(&self, fleet_index: u8) -> String {
+        let ship_class = self.get_ship_class(fleet_index);
+        let ships_with_name = self
+            .ships
+            .iter()
+            .filter(|ship| ship.name == ship_class.name)
+            .count();
+
+        if ships_with_name > 1 {
+            let number = self.ships[..=fleet_index as usize]
+                .iter()
+                .filter(|ship| ship.name == ship_class.name)
+                .count();
+            format!("{} {} ({})", ship_class.name, number, ship_class.length)
+        } else {
+            format!("{} ({})", ship_class.name, ship_class.length)
+        }
+    }
+
+    pub fn get_total_ship_tiles(&self) -> u32 {
+        self.ships.iter().map(|ship| ship.length as u32).sum()
+    }
+
+    pub fn get_smallest_ship_length(&self) -> u8 {
+        self.ships.iter().map(|ship| ship.length).min().unwrap_or(0)
+    }
+
+    /// Quick checks every fleet that fits has to pass: each ship is at least 1 long and as long as a
+    /// side of the board at most, and the ships do not have more tiles than the board. Passing them does not mean the
+    /// ships can all be packed onto the board together, see
+    /// [`check_fleet_fits`](crate::game::computer::computer_setup::check_fleet_fits).
+    pub fn fits_on(&self, board_size: BoardSize) -> bool {
+        let longest_side = board_size.get_width().max(board_size.get_height()) as u8;
+        let board_tiles = board_size.get_width() as u32 * board_size.get_height() as u32;
+
+        !self.ships.is_empty()
+            && self.ships.len() <= u8::MAX as usize
+            && self
+                .ships
+                .iter()
+                .all(|ship| ship.length > 0 && ship.length <= longest_side)
+            && self.get_total_ship_tiles() <= board_tiles
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PlacedShip {
+    pub ship: Ship,
+    pub position: Position,
+    pub hits: u8,
+}
+
+impl PlacedShip {
+    pub fn is_sunk(&self) -> bool {
+        self.hits >= self.ship.get_ship_length()
+    }
+}
+
+/// The ships that have actually been placed on a board, with their hit state.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Fleet {
+    definition: FleetDefinition,
+    ships: Vec<Option<PlacedShip>>,
+    ships_afloat: usize,
+}
+
+impl Fleet {
+    pub fn new(definition: FleetDefinition) -> Self {
+        let ships = vec![None; definition.ships.len()];
+
+        Self {
+            definition,
+            ships,
+            ships_afloat: 0,
+        }
+    }
+
+    pub fn get_definition(&self) -> &FleetDefinition {
+        &self.definition
+    }
+
+    pub fn get_ship_class(&self, id: ShipId) -> &ShipClass {
+        self.definition.get_ship_class(id.0)
+    }
+
+    pub fn get_placed_ship(&self, id: ShipId) -> Option<&PlacedShip> {
+        self.ships.get(id.0 as usize).and_then(|ship| ship.as_ref())
+    }
+
+    pub fn placed_ships(&self) -> impl Iterator<Item = &PlacedShip> {
+        self.ships.iter().flatten()
+    }
+
+    pub fn is_placed(&self, id: ShipId) -> bool {
+        self.get_placed_ship(id).is_some()
+    }
+
+    pub fn all_ships_placed(&self) -> bool {
+        self.ships.iter().all(|ship| ship.is_some())
+    }
+
+    pub fn get_ships_afloat(&self) -> usize {
+        self.ships_afloat
+    }
+
+    pub fn all_ships_sunk(&self) -> bool {
+        self.ships_afloat == 0
+    }
+
+    pub fn add_placed_ship(&mut self, ship: Ship, position: Position) {
+        self.ships[ship.get_id().0 as usize] = Some(PlacedShip {
+            ship,
+            position,
+            hits: 0,
+        });
+        self.ships_afloat += 1;
+    }
+
+    // returns true when the hit sunk the ship
+    pub fn register_hit(&mut self, id: ShipId) -> bool {
+        let Some(placed_ship) = self
+            .ships
+            .get_mut(id.0 as usize)
+            .and_then(|ship| ship.as_mut())
+        else {
+            return false;
+        };
+
+        if placed_ship.is_sunk() {
+            return false;
+        }
+
+        placed_ship.hits += 1;
+
+        if placed_ship.is_sunk() {
+            self.ships_afloat -= 1;
+            return true;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_names_and_lengths() {
+        let fleet =
+            FleetDefinition::parse("Custom", "Carrier:5, Patrol Boat:2\nCruiser:3").unwrap();
+
+        assert_eq!(
+            fleet.ships,
+            vec![
+                ShipClass::new("Carrier", 5),
+                ShipClass::new("Patrol Boat", 2),
+                ShipClass::new("Cruiser", 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_fleets() {
+        assert!(FleetDefinition::parse("Custom", "").is_err());
+        assert!(FleetDefinition::parse("Custom", "Carrier").is_err());
+        assert!(FleetDefinition::parse("Custom", "Carrier:five").is_err());
+        assert!(FleetDefinition::parse("Custom", "Carrier:0").is_err());
+        assert!(FleetDefinition::parse("Custom", "Carrier:256").is_err());
+    }
+
+    #[test]
+    fn fits_on_checks_lengths_and_tiles() {
+        let board_size = BoardSize::new(10, 6);
+
+        assert!(FleetDefinition::classic().fits_on(board_size));
+        // longer than the board is high, but it can lie along the width
+        assert!(FleetDefinition::new("Long", vec![ShipClass::new("Long", 8)]).fits_on(board_size));
+        assert!(
+            !FleetDefinition::new("Long", vec![ShipClass::new("Long", 11)]).fits_on(board_size)
+        );
+        assert!(!FleetDefinition::new("Empty", vec![]).fits_on(board_size));
+        assert!(!FleetDefinition::new("Zero", vec![ShipClass::new("Zero", 0)]).fits_on(board_size));
+
+        let crowded = FleetDefinition::new("Crowded", vec![ShipClass::new("Carrier", 5); 13]);
+        assert!(!crowded.fits_on(board_size));
+    }
+
+    #[test]
+    fn get_ship_label_numbers_ships_that_share_a_name() {
+        let fleet = FleetDefinition::russian();
+
+        assert_eq!(fleet.get_ship_label(0), "Battleship (4)");
+        assert_eq!(fleet.get_ship_label(2), "Cruiser 2 (3)");
+    }
+}
@@ -0,0 +1,237 @@
+use crate::game::save::parse_board_size;
+use crate::{BoardSize, GameBoard, Position, Tile};
+use std::fs;
+
+pub const HEATMAP_FILE_PATH: &str = "battleship.heatmaps";
+const HEATMAP_FORMAT_HEADER: &str = "battleship-heatmaps 1";
+// the profile used when the player does not name one
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// How often each tile held one of a player's ships, over every finished game of a profile on one
+/// board size.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PlacementHeatmap {
+    profile: String,
+    size: BoardSize,
+    games: u32,
+    ship_counts: Vec<Vec<u32>>,
+}
+
+impl PlacementHeatmap {
+    pub fn new(profile: &str, size: BoardSize) -> Self {
+        Self {
+            profile: profile.to_string(),
+            size,
+            games: 0,
+            ship_counts: vec![vec![0; size.get_width() as usize]; size.get_height() as usize],
+        }
+    }
+
+    /// The heatmap of the profile for the board size, an empty one when the profile has not
+    /// finished a game on it yet.
+    pub fn load(profile: &str, size: BoardSize) -> Result<Self, String> {
+        let heatmap = load_heatmaps()?
+            .into_iter()
+            .find(|heatmap| heatmap.profile == profile && heatmap.size == size)
+            .unwrap_or_else(|| Self::new(profile, size));
+
+        Ok(heatmap)
+    }
+
+    // replaces the heatmap of the same profile and board size, keeping every other one
+    pub fn save(&self) -> Result<(), String> {
+        let mut heatmaps = load_heatmaps()?;
+        heatmaps.retain(|heatmap| heatmap.profile != self.profile || heatmap.size != self.size);
+        heatmaps.push(self.clone());
+
+        let mut lines = vec![HEATMAP_FORMAT_HEADER.to_string()];
+        for heatmap in heatmaps.iter() {
+            lines.extend(heatmap.to_heatmap_lines());
+        }
+
+        fs::write(HEATMAP_FILE_PATH, lines.join("\n") + "\n")
+            .map_err(|error| format!("Could not write {}: {}", HEATMAP_FILE_PATH, error))
+    }
+
+    pub fn get_games(&self) -> u32 {
+        self.games
+    }
+
+    /// The share of finished games in which the tile held a ship, 0 before the first game.
+    pub fn get_ship_frequency(&self, position: Position) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+
+        self.ship_counts[position.get_y() as usize][position.get_x() as usize] as f64
+            / self.games as f64
+    }
+
+    /// Adds the ships of a finished game's board, sunk or not.
+    pub fn record_board(&mut self, board: &GameBoard) {
+        if board.get_size() != self.size {
+            return;
+        }
+
+        for (y, row) in board.board.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                if matches!(tile, Tile::Ship(_) | Tile::Hit) {
+                    self.ship_counts[y][x] += 1;
+                }
+            }
+        }
+
+        self.games += 1;
+    }
+
+    fn to_heatmap_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "heatmap {} {}x{} {}",
+            self.profile,
+            self.size.get_width(),
+            self.size.get_height(),
+            self.games
+        )];
+
+        for row in self.ship_counts.iter() {
+            let counts: Vec<String> = row.iter().map(|count| count.to_string()).collect();
+            lines.push(counts.join(" "));
+        }
+
+        lines
+    }
+}
+
+fn load_heatmaps() -> Result<Vec<PlacementHeatmap>, String> {
+    // no file just means no game has been finished yet
+    let Ok(heatmaps) = fs::read_to_string(HEATMAP_FILE_PATH) else {
+        return Ok(Vec::new());
+    };
+
+    parse_heatmaps(&heatmaps)
+}
+
+fn parse_heatmaps(heatmaps: &str) -> Result<Vec<PlacementHeatmap>, String> {
+    let invalid = || format!("{} is not a valid heatmap file", HEATMAP_FILE_PATH);
+
+    let mut lines = heatmaps.lines();
+    if lines.next() != Some(HEATMAP_FORMAT_HEADER) {
+        return Err(invalid());
+    }
+
+    let mut parsed_heatmaps = Vec::new();
+    while let Some(line) = lines.next() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let ["heatmap", profile, size, games] = parts[..] else {
+            return Err(invalid());
+        };
+        let size = parse_board_size(size).ok_or_else(invalid)?;
+        let games = games.parse::<u32>().map_err(|_| invalid())?;
+
+        let mut ship_counts = Vec::new();
+        for _ in 0..size.get_height() {
+            let row = lines
+                .next()
+                .ok_or_else(invalid)?
+                .split_whitespace()
+                .map(|count| count.parse::<u32>().map_err(|_| invalid()))
+                .collect::<Result<Vec<u32>, String>>()?;
+
+            if row.len() != size.get_width() as usize {
+                return Err(invalid());
+            }
+            ship_counts.push(row);
+        }
+
+        parsed_heatmaps.push(PlacementHeatmap {
+            profile: profile.to_string(),
+            size,
+            games,
+            ship_counts,
+        });
+    }
+
+    Ok(parsed_heatmaps)
+}
+
+// adds the player's ships of a finished game to their profile, a problem with the file is only
+// reported as the game itself is already over
+pub fn record_placement(profile: &str, board: &GameBoard) {
+    let result = PlacementHeatmap::load(profile, board.get_size()).and_then(|mut heatmap| {
+        heatmap.record_board(board);
+        heatmap.save()
+    });
+
+    if let Err(error) = result {
+        println!("{}", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FleetDefinition, Ship, ShipId, ShipOrientation};
+
+    // a two tile ship in the top left corner of a 6x6 board
+    fn board() -> GameBoard {
+        let mut board = GameBoard::new(
+            BoardSize::new(6, 6),
+            FleetDefinition::parse("Test", "Sub:2").unwrap(),
+        );
+        let ship = Ship::new(ShipId(0), 2, ShipOrientation::Horizontal);
+        board.place_ship(ship, Position::new(0, 0)).unwrap();
+
+        board
+    }
+
+    #[test]
+    fn heatmap_lines_parse_back() {
+        let mut heatmap = PlacementHeatmap::new("alice", BoardSize::new(6, 6));
+        heatmap.record_board(&board());
+        heatmap.record_board(&board());
+        let other_heatmap = PlacementHeatmap::new("bob", BoardSize::new(10, 10));
+
+        let mut lines = vec![HEATMAP_FORMAT_HEADER.to_string()];
+        lines.extend(heatmap.to_heatmap_lines());
+        lines.extend(other_heatmap.to_heatmap_lines());
+        let parsed_heatmaps = parse_heatmaps(&(lines.join("\n") + "\n")).unwrap();
+
+        assert_eq!(parsed_heatmaps, vec![heatmap, other_heatmap]);
+        assert_eq!(parsed_heatmaps[0].get_games(), 2);
+        assert_eq!(
+            parsed_heatmaps[0].get_ship_frequency(Position::new(0, 1)),
+            1.0
+        );
+        assert_eq!(
+            parsed_heatmaps[0].get_ship_frequency(Position::new(1, 1)),
+            0.0
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed_heatmaps() {
+        let rows = "1 1 0 0 0 0\n".to_string() + &"0 0 0 0 0 0\n".repeat(5);
+        let valid = format!("battleship-heatmaps 1\nheatmap alice 6x6 1\n{}", rows);
+        assert!(parse_heatmaps(&valid).is_ok());
+
+        for malformed in [
+            valid.replace("battleship-heatmaps 1", "battleship-heatmaps 2"),
+            valid.replace("6x6 1", "6x6"),
+            valid.replace("6x6", "2x2"),
+            valid.replace("6x6 1", "6x6 -1"),
+            valid.replace("1 1 0", "1 x 0"),
+            valid.replace("1 1 0 0 0 0", "1 1 0 0 0 0 0"),
+            valid.replacen("0 0 0 0 0 0\n", "", 1),
+        ] {
+            assert!(parse_heatmaps(&malformed).is_err(), "{}", malformed);
+        }
+    }
+
+    #[test]
+    fn boards_of_another_size_are_not_recorded() {
+        let mut heatmap = PlacementHeatmap::new("alice", BoardSize::new(10, 10));
+        heatmap.record_board(&board());
+
+        assert_eq!(heatmap.get_games(), 0);
+    }
+}
@@ -1,33 +1,86 @@
+pub mod benchmark;
+pub mod commitment;
 pub mod computer;
+pub mod engine;
+pub mod export;
+pub mod fleet;
+pub mod heatmap;
 pub mod multiplayer;
+pub mod network;
+pub mod observed_board;
+pub mod placement_benchmark;
 pub mod player;
+pub mod record;
+pub mod replay;
+pub mod save;
 pub mod simulation;
 pub mod singleplayer;
+pub mod statistics;
+pub mod tournament;
 
-use crate::{Board, GameBoard, Position, Ship, ShipType, Tile, GRID_SIZE};
+use crate::{Board, GameBoard, Position, Ship, ShipOrientation, Tile};
+use fleet::ShipClass;
 
+/// Everything [`process_attack`] found out about a shot, see [`AttackFeedback::get_shot_outcome`].
 pub struct AttackFeedback {
-    tile_at_attack: Tile,
     valid_attack: bool,
-    sunk_a_ship: bool,
     hit_a_ship: bool,
     won_the_game: bool,
+    sunk_ship: Option<ShipClass>,
 }
 
 impl AttackFeedback {
     pub fn new(
-        tile_at_attack: Tile,
         valid_attack: bool,
-        sunk_a_ship: bool,
         hit_a_ship: bool,
         won_the_game: bool,
+        sunk_ship: Option<ShipClass>,
     ) -> Self {
         Self {
-            tile_at_attack,
             valid_attack,
-            sunk_a_ship,
             hit_a_ship,
             won_the_game,
+            sunk_ship,
+        }
+    }
+
+    pub fn get_sunk_ship(&self) -> Option<&ShipClass> {
+        self.sunk_ship.as_ref()
+    }
+
+    /// None when the attack was not valid, as nothing happened.
+    pub fn get_shot_outcome(&self) -> Option<ShotOutcome> {
+        if !self.valid_attack {
+            return None;
+        }
+
+        let outcome = match (&self.sunk_ship, self.won_the_game) {
+            (Some(sunk_ship), true) => ShotOutcome::Won(sunk_ship.clone()),
+            (Some(sunk_ship), false) => ShotOutcome::Sunk(sunk_ship.clone()),
+            _ if self.hit_a_ship => ShotOutcome::Hit,
+            _ => ShotOutcome::Miss,
+        };
+
+        Some(outcome)
+    }
+}
+
+/// What a valid shot did, the ship class is the one that was sunk.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ShotOutcome {
+    Miss,
+    Hit,
+    Sunk(ShipClass),
+    Won(ShipClass),
+}
+
+impl ShotOutcome {
+    pub fn get_outcome_description(&self) -> String {
+        match self {
+            ShotOutcome::Miss => "miss".to_string(),
+            ShotOutcome::Hit => "hit".to_string(),
+            ShotOutcome::Sunk(ship) => format!("sunk the {}", ship.name),
+            ShotOutcome::Won(ship) => format!("sunk the {} and won the game", ship.name),
         }
     }
 }
@@ -40,23 +93,23 @@ pub fn place_ship_on_board(
     col: usize,
     render: bool,
 ) -> (bool, Board) {
-    match ship.ship_type {
-        ShipType::CarrierHorizontal
-        | ShipType::BattleshipHorizontal
-        | ShipType::CruiserHorizontal
-        | ShipType::SubmarineHorizontal
-        | ShipType::DestroyerHorizontal => {
-            if col + ship.ship_type.get_ship_length() as usize <= GRID_SIZE as usize {
-                let mut valid = true;
-                for i in col..col + ship.ship_type.get_ship_length() as usize {
-                    if board[row][i] != Tile::Unknown && !render {
-                        valid = false;
-                    }
-                }
+    // checked first, so that adding the ship length can not overflow
+    if row >= board.len() || col >= board[row].len() {
+        return (false, board);
+    }
+
+    match ship.orientation {
+        ShipOrientation::Horizontal => {
+            if row < board.len() && col + ship.get_ship_length() as usize <= board[row].len() {
+                let ship_tiles = col..col + ship.get_ship_length() as usize;
+                let valid = render
+                    || board[row][ship_tiles.clone()]
+                        .iter()
+                        .all(|tile| *tile == Tile::Unknown);
 
                 if valid {
-                    for i in col..col + ship.ship_type.get_ship_length() as usize {
-                        board[row][i] = Tile::Ship(ship.ship_type);
+                    for tile in board[row][ship_tiles].iter_mut() {
+                        *tile = Tile::Ship(*ship);
                     }
                     (true, board)
                 } else {
@@ -66,18 +119,17 @@ pub fn place_ship_on_board(
                 (false, board)
             }
         }
-        _ => {
-            if row + ship.ship_type.get_ship_length() as usize <= GRID_SIZE as usize {
-                let mut valid = true;
-                for i in row..row + ship.ship_type.get_ship_length() as usize {
-                    if board[i][col] != Tile::Unknown && !render {
-                        valid = false;
-                    }
-                }
+        ShipOrientation::Vertical => {
+            if row + ship.get_ship_length() as usize <= board.len() && col < board[row].len() {
+                let ship_rows = row..row + ship.get_ship_length() as usize;
+                let valid = render
+                    || board[ship_rows.clone()]
+                        .iter()
+                        .all(|board_row| board_row[col] == Tile::Unknown);
 
                 if valid {
-                    for i in row..row + ship.ship_type.get_ship_length() as usize {
-                        board[i][col] = Tile::Ship(ship.ship_type);
+                    for board_row in board[ship_rows].iter_mut() {
+                        board_row[col] = Tile::Ship(*ship);
                     }
                     (true, board)
                 } else {
@@ -90,38 +142,73 @@ pub fn place_ship_on_board(
     }
 }
 
-pub fn process_attack(defender_board: GameBoard, attack_position: Position) -> AttackFeedback {
+/// Applies the attack to the defender board, marking the tile and updating the hit ship.
+pub fn process_attack(defender_board: &mut GameBoard, attack_position: Position) -> AttackFeedback {
+    if !attack_position.is_on_board(defender_board.get_size()) {
+        return AttackFeedback::new(false, false, false, None);
+    }
+
     let tile_at_attack_position = defender_board.get_tile_at_position(attack_position);
 
-    let valid_attack = match tile_at_attack_position {
-        Tile::Unknown | Tile::Ship(_) => true,
-        _ => false,
-    };
+    match tile_at_attack_position {
+        Tile::Unknown => {
+            defender_board.place_marker_on_board(attack_position, Tile::Miss);
+            AttackFeedback::new(true, false, false, None)
+        }
+        Tile::Ship(ship) => {
+            defender_board.place_marker_on_board(attack_position, Tile::Hit);
 
-    if !valid_attack {
-        return AttackFeedback::new(tile_at_attack_position, false, false, false, false);
-    }
+            let attack_sunk_a_ship = defender_board.register_hit(ship.get_id());
+            let attack_won_the_game = defender_board.get_fleet().all_ships_sunk();
 
-    match tile_at_attack_position {
-        Tile::Unknown => AttackFeedback::new(tile_at_attack_position, true, false, false, false),
-        Tile::Ship(_) => {
-            let attack_sunk_a_ship = defender_board.check_if_hit_is_a_sink(tile_at_attack_position);
-            let attack_won_the_game =
-                defender_board.check_if_hit_won_the_game(tile_at_attack_position);
-
-            let hit_a_ship = match tile_at_attack_position {
-                Tile::Ship(_) => true,
-                _ => false,
+            let sunk_ship = if attack_sunk_a_ship {
+                Some(
+                    defender_board
+                        .get_fleet()
+                        .get_ship_class(ship.get_id())
+                        .clone(),
+                )
+            } else {
+                None
             };
 
-            AttackFeedback::new(
-                tile_at_attack_position,
-                true,
-                attack_sunk_a_ship,
-                hit_a_ship,
-                attack_won_the_game,
-            )
+            AttackFeedback::new(true, true, attack_won_the_game, sunk_ship)
         }
-        _ => AttackFeedback::new(tile_at_attack_position, false, false, false, false),
+        _ => AttackFeedback::new(false, false, false, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShipId;
+
+    #[test]
+    fn place_ship_on_board_refuses_positions_off_the_board() {
+        let board = vec![vec![Tile::Unknown; 10]; 6];
+        let ship = Ship::new(ShipId(0), 8, ShipOrientation::Vertical);
+
+        // a selector position of -2 cast to usize
+        let (placed, _) = place_ship_on_board(board.clone(), &ship, usize::MAX - 1, 0, true);
+        assert!(!placed);
+        let (placed, _) = place_ship_on_board(board.clone(), &ship, 0, usize::MAX - 1, true);
+        assert!(!placed);
+        // longer than the board is high
+        let (placed, _) = place_ship_on_board(board, &ship, 0, 0, false);
+        assert!(!placed);
+    }
+
+    #[test]
+    fn place_ship_on_board_refuses_overlapping_ships() {
+        let board = vec![vec![Tile::Unknown; 10]; 10];
+        let first = Ship::new(ShipId(0), 3, ShipOrientation::Horizontal);
+        let second = Ship::new(ShipId(1), 3, ShipOrientation::Vertical);
+
+        let (placed, board) = place_ship_on_board(board, &first, 2, 2, false);
+        assert!(placed);
+        assert_eq!(board[2][4], Tile::Ship(first));
+
+        let (placed, _) = place_ship_on_board(board, &second, 0, 3, false);
+        assert!(!placed);
     }
 }
@@ -1,55 +1,123 @@
 use crate::display::{game::display_game_board, inputs::Confirm};
-use crate::game::player::{player_setup::player_setup, player_turn};
+use crate::game::engine::Game;
+use crate::game::fleet::FleetDefinition;
+use crate::game::player::{player_setup::player_setup, player_turn, PlayerTurnResult};
+use crate::game::record::{resume_game_record, write_game_records, GameRecord};
+use crate::game::save::GameSnapshot;
+use crate::game::ShotOutcome;
 use crate::utils::terminal::refresh_display;
-use crate::Player;
+use crate::{BoardSize, Player};
+
+pub fn multiplayer_game(
+    board_size: BoardSize,
+    fleet: FleetDefinition,
+    record_path: Option<String>,
+) {
+    let mut game = Game::new(board_size, fleet.clone());
+
+    for player in [Player::PlayerOne, Player::PlayerTwo] {
+        let board = player_setup(player, board_size, &fleet);
+        if let Err(error) = game.place_fleet(player, board) {
+            panic!(
+                "the fleet of {} could not be placed: {}",
+                player.get_player_name(),
+                error
+            );
+        }
+    }
 
-pub fn multiplayer_game() {
-    let player_one_board = player_setup(Player::PlayerOne);
-    let player_two_board = player_setup(Player::PlayerTwo);
+    play_multiplayer_game(game, record_path, None);
+}
+
+pub fn resume_multiplayer_game(snapshot: GameSnapshot) {
+    match Game::resume(
+        snapshot.player_one_board,
+        snapshot.player_two_board,
+        snapshot.current_player,
+    ) {
+        Ok(game) => {
+            GameSnapshot::remove_save();
+            let (record_path, game_record) = resume_game_record(
+                snapshot.record_path,
+                game.get_board(Player::PlayerOne),
+                game.get_board(Player::PlayerTwo),
+            );
+            play_multiplayer_game(game, record_path, game_record);
+        }
+        Err(error) => println!("The saved game can not be resumed: {}", error),
+    }
+}
 
-    let mut current_player = Player::PlayerOne;
+fn play_multiplayer_game(
+    mut game: Game,
+    record_path: Option<String>,
+    saved_record: Option<GameRecord>,
+) {
+    let mut game_record = saved_record.unwrap_or_else(|| {
+        GameRecord::new(
+            &Player::PlayerOne.get_player_name(),
+            &Player::PlayerTwo.get_player_name(),
+            game.get_board(Player::PlayerOne),
+            game.get_board(Player::PlayerTwo),
+        )
+    });
 
     let mut confirm = false;
     while !confirm {
         confirm = Confirm::new()
-            .set_message(
-                "The game is about to begin, make sure player one has the computer".to_string(),
-            )
+            .set_message(format!(
+                "The game is about to begin, make sure {} has the computer",
+                game.current_turn().get_player_name()
+            ))
             .ask();
     }
 
-    let mut attacker_board = player_one_board.clone();
-    let mut defender_board = player_two_board.clone();
-
     // the 4 is for the lines of numbers at the top of boards, and board labels
-    let mut refresh_amount = defender_board.board.len() + attacker_board.board.len() + 4;
+    let board_lines = game.get_board_size().get_height() as usize * 2 + 4;
+    let mut refresh_amount = board_lines;
 
     loop {
+        let current_player = game.current_turn();
         let other_player = current_player.get_other_player().get_player_name();
 
-        let player_turn_result = player_turn(
-            defender_board,
-            &other_player,
-            attacker_board,
-            refresh_amount,
-        );
-
-        defender_board = player_turn_result.defender_board;
-
-        if player_turn_result.sunk_a_ship {
-            println!("");
-            println!(
-                "You sunk {}'s {}!",
-                other_player,
-                player_turn_result.tile_at_attack.get_tile_type_name()
-            );
-            println!("");
+        let (attack_position, shot_outcome) =
+            match player_turn(&mut game, &other_player, refresh_amount) {
+                PlayerTurnResult::Fired {
+                    attack_position,
+                    shot_outcome,
+                } => (attack_position, shot_outcome),
+                PlayerTurnResult::SaveAndQuit => {
+                    write_game_records(record_path.as_deref(), &[game_record]);
+
+                    let snapshot = GameSnapshot {
+                        current_player,
+                        player_one_board: game.get_board(Player::PlayerOne).clone(),
+                        player_two_board: game.get_board(Player::PlayerTwo).clone(),
+                        computer: None,
+                        record_path: record_path.clone(),
+                    };
+
+                    match snapshot.save() {
+                        Ok(()) => println!("Game saved, pick \"Resume saved game\" to continue it"),
+                        Err(error) => println!("{}", error),
+                    }
+                    break;
+                }
+            };
+
+        game_record.add_shot(current_player, attack_position, shot_outcome.clone());
+
+        if let ShotOutcome::Sunk(sunk_ship) | ShotOutcome::Won(sunk_ship) = &shot_outcome {
+            println!();
+            println!("You sunk {}'s {}!", other_player, sunk_ship.name);
+            println!();
 
             refresh_amount += 3;
         }
 
-        if player_turn_result.won_the_game {
+        if game.winner() == Some(current_player) {
             println!("{} won the game!", current_player.get_player_name());
+            write_game_records(record_path.as_deref(), &[game_record]);
             break;
         }
 
@@ -65,9 +133,9 @@ pub fn multiplayer_game() {
 
         refresh_display(refresh_amount as u16);
         println!("{}'s board", other_player);
-        display_game_board(defender_board, true);
+        display_game_board(game.get_board(current_player.get_other_player()), true);
         println!("Your board");
-        display_game_board(attacker_board, true);
+        display_game_board(game.get_board(current_player), true);
 
         confirm = false;
         while !confirm {
@@ -79,13 +147,8 @@ pub fn multiplayer_game() {
                 .ask();
         }
 
-        refresh_amount = defender_board.board.len() + attacker_board.board.len() + 4;
+        refresh_amount = board_lines;
 
         refresh_display(refresh_amount as u16);
-
-        current_player = current_player.get_other_player();
-        let temp = attacker_board;
-        attacker_board = defender_board;
-        defender_board = temp;
     }
 }
@@ -0,0 +1,629 @@
+use crate::display::game::{display_game_board, get_position_label};
+use crate::game::commitment::{verify_commitment, BoardCommitment};
+use crate::game::computer::computer_setup::check_fleet_fits;
+use crate::game::fleet::{FleetDefinition, ShipClass};
+use crate::game::player::{aim_attack, player_setup::player_setup};
+use crate::game::record::{format_shot_outcome, parse_shot_outcome};
+use crate::game::save::{
+    format_position, orientation_to_string, parse_board_size, parse_placement, parse_position,
+    parse_ship_class, place_saved_ships, SavedPlacement,
+};
+use crate::game::{process_attack, ShotOutcome};
+use crate::utils::terminal::refresh_display;
+use crate::{BoardSize, GameBoard, Player, Position, Tile};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+
+pub const PROTOCOL_VERSION: u32 = 2;
+pub const DEFAULT_PORT: u16 = 7878;
+
+// every message is a single line, the host sends the rules and always fires first
+//
+//   hello <version>                  both, before anything else
+//   size <width>x<height>            host, the rules of the game
+//   fleet <name>
+//   ship <length> <name>             once for every ship of the fleet
+//   start                            host, end of the rules
+//   commit <hash>                    both, once their ships are placed
+//   fire <y>,<x>                     attacker
+//   result <outcome>                 defender, such as "miss" or "sunk 2 Destroyer"
+//   salt <salt>                      both, only once the game is over
+//   placed <id> <y>,<x> <orientation> both, the ships the commitment was made for
+//   revealed                         both, end of the placed ships
+#[derive(Debug, PartialEq, Clone)]
+pub enum Message {
+    Hello(u32),
+    Size(BoardSize),
+    Fleet(String),
+    Ship(ShipClass),
+    Start,
+    Commit(String),
+    Fire(Position),
+    Result(ShotOutcome),
+    Salt(String),
+    Placed(SavedPlacement),
+    Revealed,
+}
+
+impl Message {
+    pub fn to_line(&self) -> String {
+        match self {
+            Message::Hello(version) => format!("hello {}", version),
+            Message::Size(board_size) => {
+                format!(
+                    "size {}x{}",
+                    board_size.get_width(),
+                    board_size.get_height()
+                )
+            }
+            Message::Fleet(name) => format!("fleet {}", name),
+            Message::Ship(ship_class) => format!("ship {} {}", ship_class.length, ship_class.name),
+            Message::Start => "start".to_string(),
+            Message::Commit(hash) => format!("commit {}", hash),
+            Message::Fire(position) => format!("fire {}", format_position(*position)),
+            Message::Result(outcome) => format!("result {}", format_shot_outcome(outcome)),
+            Message::Salt(salt) => format!("salt {}", salt),
+            Message::Placed((id, position, orientation)) => format!(
+                "placed {} {} {}",
+                id.0,
+                format_position(*position),
+                orientation_to_string(*orientation)
+            ),
+            Message::Revealed => "revealed".to_string(),
+        }
+    }
+
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        let invalid = || format!("Invalid message \"{}\"", line);
+
+        let message = match key {
+            "hello" => Message::Hello(value.parse().map_err(|_| invalid())?),
+            "size" => Message::Size(parse_board_size(value).ok_or_else(invalid)?),
+            "fleet" => Message::Fleet(value.to_string()),
+            "ship" => Message::Ship(parse_ship_class(value).ok_or_else(invalid)?),
+            "start" => Message::Start,
+            "commit" if is_hex(value) => Message::Commit(value.to_string()),
+            "fire" => Message::Fire(parse_position(value).ok_or_else(invalid)?),
+            "result" => Message::Result(parse_shot_outcome(value).ok_or_else(invalid)?),
+            "salt" if is_hex(value) => Message::Salt(value.to_string()),
+            "placed" => Message::Placed(parse_placement(value).ok_or_else(invalid)?),
+            "revealed" => Message::Revealed,
+            _ => return Err(invalid()),
+        };
+
+        Ok(message)
+    }
+}
+
+pub struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    // waits for one player to connect
+    pub fn host(address: &str) -> Result<Self, String> {
+        let listener = TcpListener::bind(address)
+            .map_err(|error| format!("Could not listen on {}: {}", address, error))?;
+        let (stream, _) = listener
+            .accept()
+            .map_err(|error| format!("Could not accept a player: {}", error))?;
+
+        Self::from_stream(stream)
+    }
+
+    pub fn join(address: &str) -> Result<Self, String> {
+        let stream = TcpStream::connect(address)
+            .map_err(|error| format!("Could not connect to {}: {}", address, error))?;
+
+        Self::from_stream(stream)
+    }
+
+    fn from_stream(stream: TcpStream) -> Result<Self, String> {
+        let writer = stream
+            .try_clone()
+            .map_err(|error| format!("Could not set up the connection: {}", error))?;
+
+        Ok(Self {
+            reader: BufReader::new(stream),
+            writer,
+        })
+    }
+
+    pub fn send(&mut self, message: &Message) -> Result<(), String> {
+        writeln!(self.writer, "{}", message.to_line())
+            .and_then(|_| self.writer.flush())
+            .map_err(|_| "The other player left the game".to_string())
+    }
+
+    pub fn receive(&mut self) -> Result<Message, String> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) | Err(_) => Err("The other player left the game".to_string()),
+            Ok(_) => Message::parse(line.trim_end_matches(['\r', '\n'])),
+        }
+    }
+
+    // both sides say hello first, so mismatched versions are caught before anything else
+    fn handshake(&mut self) -> Result<(), String> {
+        self.send(&Message::Hello(PROTOCOL_VERSION))?;
+
+        match self.receive()? {
+            Message::Hello(PROTOCOL_VERSION) => Ok(()),
+            Message::Hello(version) => Err(format!(
+                "The other player uses protocol version {}, but this game uses version {}",
+                version, PROTOCOL_VERSION
+            )),
+            message => Err(unexpected_message(&message)),
+        }
+    }
+}
+
+fn is_hex(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|character| character.is_ascii_hexdigit())
+}
+
+fn unexpected_message(message: &Message) -> String {
+    format!("Unexpected message \"{}\"", message.to_line())
+}
+
+// an address without a port uses the default port
+pub fn get_join_address(address: &str) -> String {
+    if address.contains(':') {
+        address.to_string()
+    } else {
+        format!("{}:{}", address, DEFAULT_PORT)
+    }
+}
+
+pub fn host_game(address: &str, board_size: BoardSize, fleet: FleetDefinition) {
+    println!("Waiting for a player to join on {}...", address);
+
+    let result = Connection::host(address).and_then(|mut connection| {
+        println!("A player joined the game");
+        connection.handshake()?;
+
+        connection.send(&Message::Size(board_size))?;
+        connection.send(&Message::Fleet(fleet.name.clone()))?;
+        for ship_class in fleet.ships.iter() {
+            connection.send(&Message::Ship(ship_class.clone()))?;
+        }
+        connection.send(&Message::Start)?;
+
+        play_network_game(connection, Player::PlayerOne, board_size, fleet)
+    });
+
+    if let Err(error) = result {
+        println!("{}", error);
+    }
+}
+
+pub fn join_game(address: &str) {
+    println!("Joining the game at {}...", address);
+
+    let result = Connection::join(address).and_then(|mut connection| {
+        connection.handshake()?;
+
+        let mut board_size = None;
+        let mut fleet_name = String::new();
+        let mut ship_classes = Vec::new();
+        loop {
+            match connection.receive()? {
+                Message::Size(size) => board_size = Some(size),
+                Message::Fleet(name) => fleet_name = name,
+                Message::Ship(ship_class) => ship_classes.push(ship_class),
+                Message::Start => break,
+                message => return Err(unexpected_message(&message)),
+            }
+        }
+
+        let board_size = board_size.ok_or("The host did not send a board size")?;
+        let fleet = FleetDefinition::new(&fleet_name, ship_classes);
+        if check_fleet_fits(board_size, &fleet).is_err() {
+            return Err("The fleet of the host does not fit on the board".to_string());
+        }
+
+        println!(
+            "Playing on a {}x{} board with the {} fleet",
+            board_size.get_width(),
+            board_size.get_height(),
+            fleet.name
+        );
+        play_network_game(connection, Player::PlayerTwo, board_size, fleet)
+    });
+
+    if let Err(error) = result {
+        println!("{}", error);
+    }
+}
+
+fn play_network_game(
+    mut connection: Connection,
+    local_player: Player,
+    board_size: BoardSize,
+    fleet: FleetDefinition,
+) -> Result<(), String> {
+    let other_player = local_player.get_other_player().get_player_name();
+
+    let mut own_board = player_setup(local_player, board_size, &fleet);
+    let commitment = BoardCommitment::new(&own_board);
+    connection.send(&Message::Commit(commitment.get_hash().to_string()))?;
+    println!("Waiting for {} to place their ships...", other_player);
+    let other_commitment = match connection.receive()? {
+        Message::Commit(hash) => hash,
+        message => return Err(unexpected_message(&message)),
+    };
+    refresh_display(1);
+
+    // only the markers of our own shots, the enemy ships stay on the other computer
+    let mut enemy_board = GameBoard::new(board_size, fleet.clone());
+    // the 4 is for the lines of numbers at the top of boards, and board labels
+    let refresh_amount = enemy_board.board.len() + own_board.board.len() + 4;
+    let mut lines_on_screen = 0;
+    let mut current_player = Player::PlayerOne;
+    // what the other player claimed about each of our shots, checked once their board is revealed
+    let mut reported_outcomes = Vec::new();
+
+    let winner = loop {
+        if current_player == local_player {
+            refresh_display(lines_on_screen);
+            let Some(attack_position) = aim_attack(
+                &enemy_board,
+                &other_player,
+                &own_board,
+                refresh_amount,
+                false,
+            ) else {
+                unreachable!("saving is turned off for network games");
+            };
+
+            connection.send(&Message::Fire(attack_position))?;
+            let outcome = match connection.receive()? {
+                Message::Result(outcome) => outcome,
+                message => return Err(unexpected_message(&message)),
+            };
+
+            let marker = match outcome {
+                ShotOutcome::Miss => Tile::Miss,
+                _ => Tile::Hit,
+            };
+            enemy_board.place_marker_on_board(attack_position, marker);
+            reported_outcomes.push((attack_position, outcome.clone()));
+            if let Some(impossible_result) = find_impossible_result(&fleet, &reported_outcomes) {
+                return Err(format!(
+                    "Cheating detected: {} {}",
+                    other_player, impossible_result
+                ));
+            }
+
+            lines_on_screen = display_network_boards(
+                &enemy_board,
+                &own_board,
+                &other_player,
+                &format!(
+                    "You fired at {}, {}",
+                    get_position_label(attack_position),
+                    outcome.get_outcome_description()
+                ),
+            );
+
+            if matches!(outcome, ShotOutcome::Won(_)) {
+                break local_player;
+            }
+        } else {
+            println!("Waiting for {} to fire...", other_player);
+
+            let attack_position = match connection.receive()? {
+                Message::Fire(position) => position,
+                message => return Err(unexpected_message(&message)),
+            };
+            let outcome = process_attack(&mut own_board, attack_position)
+                .get_shot_outcome()
+                .ok_or_else(|| {
+                    format!("{} fired at a tile that can not be attacked", other_player)
+                })?;
+            connection.send(&Message::Result(outcome.clone()))?;
+
+            refresh_display(lines_on_screen + 1);
+            lines_on_screen = display_network_boards(
+                &enemy_board,
+                &own_board,
+                &other_player,
+                &format!(
+                    "{} fired at {}, {}",
+                    other_player,
+                    get_position_label(attack_position),
+                    outcome.get_outcome_description()
+                ),
+            );
+
+            if matches!(outcome, ShotOutcome::Won(_)) {
+                break local_player.get_other_player();
+            }
+        }
+
+        current_player = current_player.get_other_player();
+    };
+
+    println!("{} won the game!", winner.get_player_name());
+
+    // the fleets are only revealed once nobody can use them anymore, a fleet that is not revealed
+    // in full can not be checked, which is as suspicious as a fleet that fails the check
+    let revealed_board = reveal_fleet(&mut connection, &commitment, &own_board)
+        .and_then(|_| receive_revealed_fleet(&mut connection, board_size, &fleet));
+    let (salt, mut revealed_board) = match revealed_board {
+        Ok(revealed) => revealed,
+        Err(error) => {
+            println!(
+                "Cheating can not be ruled out, {}'s placement could not be verified: {}",
+                other_player, error
+            );
+            return Ok(());
+        }
+    };
+    let honest_commitment = verify_commitment(&other_commitment, &salt, &revealed_board);
+    let lies = find_reported_lies(&mut revealed_board, &reported_outcomes);
+
+    println!();
+    println!("{}'s fleet", other_player);
+    display_game_board(&revealed_board, false);
+
+    if !honest_commitment {
+        println!(
+            "Cheating detected: {}'s revealed fleet is not the one they committed to",
+            other_player
+        );
+        return Ok(());
+    }
+
+    if lies.is_empty() {
+        println!(
+            "{}'s fleet matches their commitment, and every result they reported was honest",
+            other_player
+        );
+    } else {
+        println!(
+            "Cheating detected, {} lied about these shots:",
+            other_player
+        );
+        for lie in lies {
+            println!("  {}", lie);
+        }
+    }
+
+    Ok(())
+}
+
+fn reveal_fleet(
+    connection: &mut Connection,
+    commitment: &BoardCommitment,
+    own_board: &GameBoard,
+) -> Result<(), String> {
+    connection.send(&Message::Salt(commitment.get_salt().to_string()))?;
+    for placed_ship in own_board.get_fleet().placed_ships() {
+        connection.send(&Message::Placed((
+            placed_ship.ship.get_id(),
+            placed_ship.position,
+            placed_ship.ship.get_orientation(),
+        )))?;
+    }
+
+    connection.send(&Message::Revealed)
+}
+
+fn receive_revealed_fleet(
+    connection: &mut Connection,
+    board_size: BoardSize,
+    fleet: &FleetDefinition,
+) -> Result<(String, GameBoard), String> {
+    let mut salt = None;
+    let mut placements = Vec::new();
+    loop {
+        match connection.receive()? {
+            Message::Salt(revealed_salt) => salt = Some(revealed_salt),
+            Message::Placed(placement) => placements.push(placement),
+            Message::Revealed => break,
+            message => return Err(unexpected_message(&message)),
+        }
+    }
+
+    let salt = salt.ok_or("the salt of the commitment was not revealed")?;
+    let revealed_board = place_saved_ships(board_size, fleet, placements)
+        .map_err(|error| format!("the revealed fleet is not allowed: {}", error))?;
+
+    Ok((salt, revealed_board))
+}
+
+// results that no placement of the fleet could have given, checked after every shot, so a player
+// who never admits to losing is caught before the reveal
+fn find_impossible_result(
+    fleet: &FleetDefinition,
+    reported_outcomes: &[(Position, ShotOutcome)],
+) -> Option<String> {
+    let total_ship_tiles = fleet.get_total_ship_tiles();
+    let mut hits = 0;
+    let mut sunk_tiles = 0;
+    let mut sunk_ships: Vec<&ShipClass> = Vec::new();
+
+    for (_, outcome) in reported_outcomes {
+        match outcome {
+            ShotOutcome::Miss => continue,
+            ShotOutcome::Hit => {}
+            ShotOutcome::Sunk(ship_class) | ShotOutcome::Won(ship_class) => {
+                sunk_tiles += ship_class.length as u32;
+                sunk_ships.push(ship_class);
+
+                let fleet_count = fleet
+                    .ships
+                    .iter()
+                    .filter(|ship| *ship == ship_class)
+                    .count();
+                let sunk_count = sunk_ships
+                    .iter()
+                    .filter(|ship| **ship == ship_class)
+                    .count();
+                if sunk_count > fleet_count {
+                    return Some(format!(
+                        "reported sinking more of their {} than their fleet has",
+                        ship_class.name
+                    ));
+                }
+            }
+        }
+        hits += 1;
+
+        if sunk_tiles > hits {
+            return Some("reported sinking a ship that was not hit often enough".to_string());
+        }
+    }
+
+    let won = matches!(reported_outcomes.last(), Some((_, ShotOutcome::Won(_))));
+    if won && sunk_ships.len() < fleet.ships.len() {
+        Some(format!(
+            "reported losing with {} of their {} ships still afloat",
+            fleet.ships.len() - sunk_ships.len(),
+            fleet.ships.len()
+        ))
+    } else if !won && hits >= total_ship_tiles {
+        Some(format!(
+            "did not report losing after all {} of their ship tiles were hit",
+            total_ship_tiles
+        ))
+    } else {
+        None
+    }
+}
+
+// replays every shot against the revealed board and describes each result that does not match
+fn find_reported_lies(
+    revealed_board: &mut GameBoard,
+    reported_outcomes: &[(Position, ShotOutcome)],
+) -> Vec<String> {
+    let mut lies = Vec::new();
+
+    for (position, reported_outcome) in reported_outcomes {
+        let Some(outcome) = process_attack(revealed_board, *position).get_shot_outcome() else {
+            continue;
+        };
+
+        if outcome != *reported_outcome {
+            lies.push(format!(
+                "{} was reported as {}, but it was {}",
+                get_position_label(*position),
+                reported_outcome.get_outcome_description(),
+                outcome.get_outcome_description()
+            ));
+        }
+    }
+
+    lies
+}
+
+// returns how many lines were printed, so they can be cleared again
+fn display_network_boards(
+    enemy_board: &GameBoard,
+    own_board: &GameBoard,
+    other_player: &String,
+    message: &str,
+) -> u16 {
+    println!("{}'s board", other_player);
+    display_game_board(enemy_board, true);
+    println!("Your board");
+    display_game_board(own_board, false);
+    println!("{}", message);
+
+    (enemy_board.board.len() + own_board.board.len() + 5) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ShipId, ShipOrientation};
+
+    fn fleet() -> FleetDefinition {
+        FleetDefinition::parse("Test", "Sub:2,Boat:1").unwrap()
+    }
+
+    fn outcomes(outcomes: &[ShotOutcome]) -> Vec<(Position, ShotOutcome)> {
+        outcomes
+            .iter()
+            .enumerate()
+            .map(|(x, outcome)| (Position::new(0, x as i8), outcome.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn messages_round_trip() {
+        let messages = [
+            Message::Hello(PROTOCOL_VERSION),
+            Message::Size(BoardSize::new(8, 6)),
+            Message::Ship(ShipClass::new("Patrol Boat", 2)),
+            Message::Commit("0a1b".to_string()),
+            Message::Fire(Position::new(3, 4)),
+            Message::Result(ShotOutcome::Sunk(ShipClass::new("Patrol Boat", 2))),
+            Message::Revealed,
+        ];
+
+        for message in messages {
+            assert_eq!(Message::parse(&message.to_line()), Ok(message));
+        }
+        assert!(Message::parse("commit not-hex").is_err());
+    }
+
+    #[test]
+    fn possible_results_are_accepted() {
+        let sub = ShipClass::new("Sub", 2);
+        let boat = ShipClass::new("Boat", 1);
+
+        let reported = outcomes(&[
+            ShotOutcome::Miss,
+            ShotOutcome::Hit,
+            ShotOutcome::Sunk(sub),
+            ShotOutcome::Won(boat),
+        ]);
+
+        assert_eq!(find_impossible_result(&fleet(), &reported), None);
+    }
+
+    #[test]
+    fn every_ship_tile_hit_without_a_win_is_impossible() {
+        let sub = ShipClass::new("Sub", 2);
+
+        let reported = outcomes(&[ShotOutcome::Hit, ShotOutcome::Sunk(sub), ShotOutcome::Hit]);
+
+        assert!(find_impossible_result(&fleet(), &reported).is_some());
+    }
+
+    #[test]
+    fn impossible_sinks_are_caught() {
+        let sub = ShipClass::new("Sub", 2);
+        let boat = ShipClass::new("Boat", 1);
+
+        // a win with a ship left, a ship sunk twice, a ship sunk before it was hit enough
+        for reported in [
+            outcomes(&[ShotOutcome::Won(boat.clone())]),
+            outcomes(&[ShotOutcome::Sunk(boat.clone()), ShotOutcome::Sunk(boat)]),
+            outcomes(&[ShotOutcome::Sunk(sub)]),
+        ] {
+            assert!(find_impossible_result(&fleet(), &reported).is_some());
+        }
+    }
+
+    #[test]
+    fn lies_are_found_against_the_revealed_board() {
+        let board_size = BoardSize::new(4, 4);
+        let placements = vec![
+            (ShipId(0), Position::new(0, 0), ShipOrientation::Horizontal),
+            (ShipId(1), Position::new(2, 0), ShipOrientation::Horizontal),
+        ];
+        let mut revealed_board = place_saved_ships(board_size, &fleet(), placements).unwrap();
+
+        let reported = vec![
+            (Position::new(0, 0), ShotOutcome::Hit),
+            (Position::new(0, 1), ShotOutcome::Miss),
+        ];
+
+        assert_eq!(find_reported_lies(&mut revealed_board, &reported).len(), 1);
+    }
+}
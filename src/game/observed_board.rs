@@ -0,0 +1,236 @@
+use crate::game::fleet::{FleetDefinition, ShipClass};
+use crate::game::ShotOutcome;
+use crate::{BoardSize, Position};
+
+/// What an attacker is allowed to know about the enemy board, built up only from attack feedback.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ObservedTile {
+    Unknown,
+    Hit,
+    Miss,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SinkAnnouncement {
+    pub ship_name: String,
+    pub ship_length: u8,
+    pub position: Position,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ObservedBoard {
+    tiles: Vec<Vec<ObservedTile>>,
+    size: BoardSize,
+    // the composition of the enemy fleet is part of the rules, so it is not hidden
+    fleet: FleetDefinition,
+    sunk_ships: Vec<SinkAnnouncement>,
+    // hits that can be deduced to belong to an already sunk ship
+    resolved_hits: Vec<Position>,
+}
+
+impl ObservedBoard {
+    pub fn new(size: BoardSize, fleet: FleetDefinition) -> Self {
+        Self {
+            tiles: vec![
+                vec![ObservedTile::Unknown; size.get_width() as usize];
+                size.get_height() as usize
+            ],
+            size,
+            fleet,
+            sunk_ships: Vec::new(),
+            resolved_hits: Vec::new(),
+        }
+    }
+
+    // rebuilds a view from a saved game
+    pub fn restore(
+        size: BoardSize,
+        fleet: FleetDefinition,
+        tiles: Vec<Vec<ObservedTile>>,
+        sunk_ships: Vec<SinkAnnouncement>,
+        resolved_hits: Vec<Position>,
+    ) -> Self {
+        Self {
+            tiles,
+            size,
+            fleet,
+            sunk_ships,
+            resolved_hits,
+        }
+    }
+
+    pub fn get_size(&self) -> BoardSize {
+        self.size
+    }
+
+    pub fn get_tile_at_position(&self, position: Position) -> ObservedTile {
+        self.tiles[position.get_y() as usize][position.get_x() as usize]
+    }
+
+    pub fn get_fleet(&self) -> &FleetDefinition {
+        &self.fleet
+    }
+
+    pub fn sunk_ships(&self) -> &[SinkAnnouncement] {
+        &self.sunk_ships
+    }
+
+    pub fn get_remaining_ships(&self) -> Vec<ShipClass> {
+        let mut remaining_ships = self.fleet.ships.clone();
+
+        for sunk_ship in self.sunk_ships.iter() {
+            if let Some(index) = remaining_ships.iter().position(|ship| {
+                ship.name == sunk_ship.ship_name && ship.length == sunk_ship.ship_length
+            }) {
+                remaining_ships.remove(index);
+            }
+        }
+
+        remaining_ships
+    }
+
+    pub fn get_smallest_remaining_ship_length(&self) -> u8 {
+        self.get_remaining_ships()
+            .iter()
+            .map(|ship| ship.length)
+            .min()
+            .unwrap_or(0)
+    }
+
+    pub fn resolved_hits(&self) -> &[Position] {
+        &self.resolved_hits
+    }
+
+    pub fn is_resolved_hit(&self, position: Position) -> bool {
+        self.resolved_hits.contains(&position)
+    }
+
+    // hits that are not yet known to be part of a sunk ship
+    pub fn unresolved_hits(&self) -> Vec<Position> {
+        let mut hits = Vec::new();
+
+        for y in 0..self.size.get_height() {
+            for x in 0..self.size.get_width() {
+                let position = Position::new(y, x);
+                if self.get_tile_at_position(position) == ObservedTile::Hit
+                    && !self.is_resolved_hit(position)
+                {
+                    hits.push(position);
+                }
+            }
+        }
+
+        hits
+    }
+
+    pub fn record_attack(&mut self, attack_position: Position, outcome: &ShotOutcome) {
+        if !attack_position.is_on_board(self.size) {
+            return;
+        }
+
+        let observed_tile = match outcome {
+            ShotOutcome::Miss => ObservedTile::Miss,
+            _ => ObservedTile::Hit,
+        };
+
+        self.tiles[attack_position.get_y() as usize][attack_position.get_x() as usize] =
+            observed_tile;
+
+        if let ShotOutcome::Sunk(sunk_ship) | ShotOutcome::Won(sunk_ship) = outcome {
+            self.sunk_ships.push(SinkAnnouncement {
+                ship_name: sunk_ship.name.clone(),
+                ship_length: sunk_ship.length,
+                position: attack_position,
+            });
+            self.resolve_sunk_ship(attack_position, sunk_ship.length as i8);
+        }
+    }
+
+    // only the sinking shot and the ship length are announced, so the rest of the ship is
+    // only marked as resolved when exactly one line of unresolved hits can explain the sink
+    fn resolve_sunk_ship(&mut self, sinking_position: Position, ship_length: i8) {
+        let mut candidates: Vec<Vec<Position>> = Vec::new();
+
+        for (y_step, x_step) in [(0, 1), (1, 0)] {
+            for offset in 0..ship_length {
+                let cells: Vec<Position> = (0..ship_length)
+                    .map(|i| {
+                        Position::new(
+                            sinking_position.get_y() + (i - offset) * y_step,
+                            sinking_position.get_x() + (i - offset) * x_step,
+                        )
+                    })
+                    .collect();
+
+                let all_unresolved_hits = cells.iter().all(|cell| {
+                    cell.is_on_board(self.size)
+                        && self.get_tile_at_position(*cell) == ObservedTile::Hit
+                        && !self.is_resolved_hit(*cell)
+                });
+
+                if all_unresolved_hits && !candidates.contains(&cells) {
+                    candidates.push(cells);
+                }
+            }
+        }
+
+        if candidates.len() == 1 {
+            self.resolved_hits.append(&mut candidates[0]);
+        } else {
+            self.resolved_hits.push(sinking_position);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view() -> ObservedBoard {
+        ObservedBoard::new(
+            BoardSize::new(6, 6),
+            FleetDefinition::parse("Test", "Cruiser:3,Destroyer:2,Boat:2").unwrap(),
+        )
+    }
+
+    #[test]
+    fn straight_sunk_ship_resolves_every_hit() {
+        let mut view = view();
+        view.record_attack(Position::new(0, 0), &ShotOutcome::Hit);
+        view.record_attack(Position::new(0, 1), &ShotOutcome::Hit);
+        view.record_attack(Position::new(3, 3), &ShotOutcome::Hit);
+        view.record_attack(
+            Position::new(0, 2),
+            &ShotOutcome::Sunk(ShipClass::new("Cruiser", 3)),
+        );
+
+        assert_eq!(
+            view.resolved_hits(),
+            &[
+                Position::new(0, 0),
+                Position::new(0, 1),
+                Position::new(0, 2)
+            ]
+        );
+        assert_eq!(view.unresolved_hits(), vec![Position::new(3, 3)]);
+        assert_eq!(view.get_remaining_ships().len(), 2);
+    }
+
+    #[test]
+    fn ambiguous_sunk_ship_only_resolves_the_sinking_shot() {
+        let mut view = view();
+        // an L of hits, the destroyer sunk at its corner could lie along either arm
+        view.record_attack(Position::new(1, 0), &ShotOutcome::Hit);
+        view.record_attack(Position::new(0, 1), &ShotOutcome::Hit);
+        view.record_attack(
+            Position::new(1, 1),
+            &ShotOutcome::Sunk(ShipClass::new("Destroyer", 2)),
+        );
+
+        assert_eq!(view.resolved_hits(), &[Position::new(1, 1)]);
+        assert_eq!(
+            view.unresolved_hits(),
+            vec![Position::new(0, 1), Position::new(1, 0)]
+        );
+    }
+}
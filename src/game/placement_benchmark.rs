@@ -0,0 +1,328 @@
+use crate::display::create_progress_bar;
+use crate::game::benchmark::{clear_board, BenchmarkResults};
+use crate::game::simulation::{get_game_seed, match_attack_strategy_to_computer, play_in_parallel};
+use crate::game::statistics::Summary;
+use crate::utils::create_rng;
+use crate::{ComputerAttackStrategy, ComputerPlacementStrategy, PlacementBenchmarkConfig};
+use rand::Rng;
+use std::ops::Range;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// How many shots every attack strategy needed to clear the boards of every placement strategy,
+/// see [`run_placement_benchmark`].
+pub struct PlacementBenchmarkResults {
+    pub placement_strategies: Vec<ComputerPlacementStrategy>,
+    pub attack_strategies: Vec<ComputerAttackStrategy>,
+    // one for every pair of placement and attack strategy, with the boards set up by the placement
+    // strategy
+    pub pairings: Vec<(ComputerPlacementStrategy, BenchmarkResults)>,
+    pub seed: u64,
+}
+
+impl PlacementBenchmarkResults {
+    pub fn get_pairing(
+        &self,
+        placement_strategy: ComputerPlacementStrategy,
+        attack_strategy: ComputerAttackStrategy,
+    ) -> Option<&BenchmarkResults> {
+        self.pairings
+            .iter()
+            .find(|(pairing_placement_strategy, pairing)| {
+                *pairing_placement_strategy == placement_strategy
+                    && pairing.attack_strategy == attack_strategy
+            })
+            .map(|(_, pairing)| pairing)
+    }
+
+    /// The mean shots the attack strategy needed to clear a board of the placement strategy, 0
+    /// when the pair was not played.
+    pub fn get_mean_shots(
+        &self,
+        placement_strategy: ComputerPlacementStrategy,
+        attack_strategy: ComputerAttackStrategy,
+    ) -> f64 {
+        self.get_pairing(placement_strategy, attack_strategy)
+            .map_or(0.0, |pairing| {
+                Summary::new(&pairing.get_shots_to_clear()).mean
+            })
+    }
+
+    /// The placement strategy the attack strategy needed the most shots to clear, the first one
+    /// listed when several are tied.
+    pub fn get_hardest_placement(
+        &self,
+        attack_strategy: ComputerAttackStrategy,
+    ) -> Option<ComputerPlacementStrategy> {
+        let mut hardest: Option<(ComputerPlacementStrategy, f64)> = None;
+
+        for placement_strategy in &self.placement_strategies {
+            let mean_shots = self.get_mean_shots(*placement_strategy, attack_strategy);
+
+            if hardest.is_none_or(|(_, most_shots)| mean_shots > most_shots) {
+                hardest = Some((*placement_strategy, mean_shots));
+            }
+        }
+
+        hardest.map(|(placement_strategy, _)| placement_strategy)
+    }
+
+    pub fn print_results(&self) {
+        println!();
+        println!("Placement Benchmark Results");
+        println!("---------------------------");
+        println!();
+
+        let boards_per_pairing = self
+            .pairings
+            .first()
+            .map_or(0, |(_, pairing)| pairing.shots_to_clear.len());
+        println!("Boards per Pairing: {}", boards_per_pairing);
+        println!("Seed: {}", self.seed);
+        println!();
+
+        self.print_shots_table();
+        self.print_hardest_placements();
+    }
+
+    fn print_shots_table(&self) {
+        let placement_width = self
+            .placement_strategies
+            .iter()
+            .map(|strategy| strategy.get_placement_strategy_name().len())
+            .max()
+            .unwrap_or(0);
+        let attack_width = self
+            .attack_strategies
+            .iter()
+            .map(|strategy| strategy.get_attack_strategy_name().len())
+            .max()
+            .unwrap_or(0);
+
+        println!("Mean Shots for Each Column to Clear Each Row");
+        println!("--------------------------------------------");
+        println!();
+
+        let header: Vec<String> = self
+            .attack_strategies
+            .iter()
+            .map(|strategy| {
+                format!(
+                    "{:>width$}",
+                    strategy.get_attack_strategy_name(),
+                    width = attack_width
+                )
+            })
+            .collect();
+        println!(
+            "{:width$} | {}",
+            "",
+            header.join(" | "),
+            width = placement_width
+        );
+
+        for placement_strategy in &self.placement_strategies {
+            let cells: Vec<String> = self
+                .attack_strategies
+                .iter()
+                .map(|attack_strategy| {
+                    let mean_shots = if self
+                        .get_pairing(*placement_strategy, *attack_strategy)
+                        .is_some()
+                    {
+                        format!(
+                            "{:.2}",
+                            self.get_mean_shots(*placement_strategy, *attack_strategy)
+                        )
+                    } else {
+                        "-".to_string()
+                    };
+
+                    format!("{:>width$}", mean_shots, width = attack_width)
+                })
+                .collect();
+
+            println!(
+                "{:width$} | {}",
+                placement_strategy.get_placement_strategy_name(),
+                cells.join(" | "),
+                width = placement_width
+            );
+        }
+        println!();
+    }
+
+    fn print_hardest_placements(&self) {
+        println!("Hardest Placement to Clear");
+        println!("--------------------------");
+        println!();
+
+        for attack_strategy in &self.attack_strategies {
+            let Some(placement_strategy) = self.get_hardest_placement(*attack_strategy) else {
+                continue;
+            };
+
+            println!(
+                "{}: {} ({:.2} shots, {:.2} for uniform placements)",
+                attack_strategy.get_attack_strategy_name(),
+                placement_strategy.get_placement_strategy_name(),
+                self.get_mean_shots(placement_strategy, *attack_strategy),
+                self.get_mean_shots(ComputerPlacementStrategy::Uniform, *attack_strategy)
+            );
+        }
+        println!();
+    }
+}
+
+pub fn placement_benchmark(placement_benchmark_config: PlacementBenchmarkConfig) {
+    println!("Clearing Boards...");
+
+    let placement_benchmark_results =
+        run_placement_benchmark(&placement_benchmark_config, create_progress_bar);
+
+    placement_benchmark_results.print_results();
+}
+
+/// Lets every attack strategy clear `games_per_pairing` boards set up by every placement strategy,
+/// with no opponent firing back.
+///
+/// Every pairing is played with the benchmark's seed, so board `n` of each pairing is placed from
+/// the same random numbers. `on_game_played` is called with the boards cleared so far over the
+/// whole benchmark and the number of boards to clear.
+pub fn run_placement_benchmark(
+    placement_benchmark_config: &PlacementBenchmarkConfig,
+    mut on_game_played: impl FnMut(i32, i32),
+) -> PlacementBenchmarkResults {
+    let seed = placement_benchmark_config.seed.unwrap_or_else(rand::random);
+
+    let mut pairing_strategies = Vec::new();
+    for placement_strategy in &placement_benchmark_config.placement_strategies {
+        for attack_strategy in &placement_benchmark_config.attack_strategies {
+            pairing_strategies.push((*placement_strategy, *attack_strategy));
+        }
+    }
+
+    let games_per_pairing = placement_benchmark_config.games_per_pairing.max(0);
+    let games_to_play = games_per_pairing * pairing_strategies.len() as i32;
+    let mut pairings = Vec::new();
+
+    for (pairing_index, (placement_strategy, attack_strategy)) in
+        pairing_strategies.into_iter().enumerate()
+    {
+        let mut pairing_results = BenchmarkResults::new(attack_strategy, seed);
+
+        let games_played_before = games_per_pairing * pairing_index as i32;
+        let worker_results = play_in_parallel(
+            games_per_pairing,
+            |games_played, _| on_game_played(games_played_before + games_played, games_to_play),
+            |games, games_played| {
+                clear_placements(
+                    placement_benchmark_config,
+                    placement_strategy,
+                    attack_strategy,
+                    seed,
+                    games,
+                    games_played,
+                )
+            },
+        );
+
+        for worker_results in worker_results {
+            pairing_results.merge(worker_results);
+        }
+
+        pairings.push((placement_strategy, pairing_results));
+    }
+
+    PlacementBenchmarkResults {
+        placement_strategies: placement_benchmark_config.placement_strategies.clone(),
+        attack_strategies: placement_benchmark_config.attack_strategies.clone(),
+        pairings,
+        seed,
+    }
+}
+
+fn clear_placements(
+    placement_benchmark_config: &PlacementBenchmarkConfig,
+    placement_strategy: ComputerPlacementStrategy,
+    attack_strategy: ComputerAttackStrategy,
+    seed: u64,
+    games: Range<i32>,
+    total_games_played: &AtomicI32,
+) -> BenchmarkResults {
+    let board_size = placement_benchmark_config.board_size;
+    let fleet = &placement_benchmark_config.fleet;
+
+    let mut computer = match_attack_strategy_to_computer(
+        &attack_strategy,
+        board_size,
+        fleet,
+        create_rng(Some(seed)),
+    )
+    .unwrap_or_else(|error| panic!("the benchmarked computer could not be set up: {}", error));
+    let mut placement = placement_strategy.get_placement_strategy();
+    let mut pairing_results = BenchmarkResults::new(attack_strategy, seed);
+
+    for game_index in games {
+        let mut game_rng = create_rng(Some(get_game_seed(seed, game_index)));
+        computer.set_rng(create_rng(Some(game_rng.gen())));
+        if let Err(error) = computer.start_new_game() {
+            panic!(
+                "the benchmarked computer's fleet could not be placed: {}",
+                error
+            );
+        }
+
+        let mut target_board = placement
+            .place_fleet(board_size, fleet, &mut game_rng)
+            .unwrap_or_else(|error| panic!("the benchmark board could not be set up: {}", error));
+        let computer_stats = clear_board(&mut computer, &mut target_board);
+
+        pairing_results
+            .shots_to_clear
+            .push(computer_stats.shots_fired);
+        total_games_played.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pairing_results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardSize, FleetDefinition};
+
+    #[test]
+    fn every_pairing_clears_its_boards() {
+        let mut config = PlacementBenchmarkConfig::new(
+            3,
+            BoardSize::new(6, 6),
+            FleetDefinition::parse("Test", "Sub:3,Boat:2").unwrap(),
+        );
+        config.seed = Some(4);
+        assert!(!config
+            .attack_strategies
+            .contains(&ComputerAttackStrategy::Hacker));
+        config.attack_strategies = vec![
+            ComputerAttackStrategy::Random,
+            ComputerAttackStrategy::HuntAndTarget,
+        ];
+
+        let results = run_placement_benchmark(&config, |_, _| {});
+
+        assert_eq!(
+            results.pairings.len(),
+            config.placement_strategies.len() * 2
+        );
+        for (_, pairing) in &results.pairings {
+            assert_eq!(pairing.shots_to_clear.len(), 3);
+            // the 5 ship tiles take at least 5 shots, and no board takes more than 36
+            assert!(pairing
+                .shots_to_clear
+                .iter()
+                .all(|shots| (5..=36).contains(shots)));
+        }
+        assert!(results
+            .get_hardest_placement(ComputerAttackStrategy::Random)
+            .is_some());
+    }
+}
@@ -1,49 +1,71 @@
 pub mod player_setup;
 
 use crate::display::game::display_game_board;
-use crate::game::process_attack;
-use crate::utils::terminal::{move_selector_position, refresh_display, Movement};
+use crate::game::{engine::Game, ShotOutcome};
+use crate::utils::terminal::{enable_raw_mode, move_selector_position, refresh_display, Movement};
 use crate::{GameBoard, Position, Tile};
 use crossterm::{
     event::{read, Event, KeyCode, KeyEvent, KeyEventKind},
     terminal,
 };
 
-pub struct PlayerTurnResult {
-    pub defender_board: GameBoard,
-    pub valid_attack: bool,
-    pub tile_at_attack: Tile,
-    pub sunk_a_ship: bool,
-    pub won_the_game: bool,
+pub enum PlayerTurnResult {
+    Fired {
+        attack_position: Position,
+        shot_outcome: ShotOutcome,
+    },
+    // the player asked to save the game and quit instead of attacking
+    SaveAndQuit,
 }
 
-impl PlayerTurnResult {
-    pub fn new(
-        defender_board: GameBoard,
-        valid_attack: bool,
-        tile_at_attack: Tile,
-        sunk_a_ship: bool,
-        won_the_game: bool,
-    ) -> PlayerTurnResult {
-        PlayerTurnResult {
-            defender_board,
-            valid_attack,
-            tile_at_attack,
-            sunk_a_ship,
-            won_the_game,
-        }
+// the player whose turn it is aims at the other player's board and fires
+pub fn player_turn(
+    game: &mut Game,
+    other_player: &String,
+    refresh_amount: usize,
+) -> PlayerTurnResult {
+    let attacker = game.current_turn();
+    let defender = attacker.get_other_player();
+
+    loop {
+        let Some(attack_position) = aim_attack(
+            game.get_board(defender),
+            other_player,
+            game.get_board(attacker),
+            refresh_amount,
+            true,
+        ) else {
+            return PlayerTurnResult::SaveAndQuit;
+        };
+
+        // aim_attack only lets the player fire at tiles that can still be attacked
+        let Ok(shot_outcome) = game.fire(attacker, attack_position) else {
+            continue;
+        };
+
+        println!("{}'s board", other_player);
+        display_game_board(game.get_board(defender), true);
+        println!("Your board");
+        display_game_board(game.get_board(attacker), false);
+
+        return PlayerTurnResult::Fired {
+            attack_position,
+            shot_outcome,
+        };
     }
 }
 
-pub fn player_turn(
-    mut defender_board: GameBoard,
+// lets the player move the selector until they fire at a tile that has not been attacked yet,
+// returns none when the player asked to save and quit instead
+pub fn aim_attack(
+    defender_board: &GameBoard,
     other_player: &String,
-    attacker_board: GameBoard,
+    attacker_board: &GameBoard,
     refresh_amount: usize,
-) -> PlayerTurnResult {
-    let mut selector_position = Position::new(4, 4);
-    let mut turn_feedback =
-        PlayerTurnResult::new(defender_board, false, Tile::Unknown, false, false);
+    can_save: bool,
+) -> Option<Position> {
+    let board_size = defender_board.get_size();
+    let mut selector_position = board_size.get_center();
 
     loop {
         let tile_to_place = match defender_board.get_tile_at_position(selector_position) {
@@ -57,11 +79,11 @@ pub fn player_turn(
         let mut defender_board_with_selector = defender_board.clone();
         defender_board_with_selector.place_marker_on_board(selector_position, tile_to_place);
         println!("{}'s board", other_player);
-        display_game_board(defender_board_with_selector, true);
+        display_game_board(&defender_board_with_selector, true);
         println!("Your board");
         display_game_board(attacker_board, false);
 
-        terminal::enable_raw_mode().expect("Failed to enable raw mode");
+        enable_raw_mode();
         selector_position = if let Ok(event) = read() {
             match event {
                 Event::Key(KeyEvent {
@@ -74,45 +96,32 @@ pub fn player_turn(
                         println!("Quitting...");
                         std::process::exit(0);
                     }
-                    KeyCode::Up => move_selector_position(selector_position, Movement::Up, 0),
-                    KeyCode::Down => move_selector_position(selector_position, Movement::Down, 0),
-                    KeyCode::Left => move_selector_position(selector_position, Movement::Left, 0),
-                    KeyCode::Right => move_selector_position(selector_position, Movement::Right, 0),
-                    KeyCode::Enter => {
+                    KeyCode::Char('s') if can_save => {
                         terminal::disable_raw_mode().expect("Failed to disable raw mode");
-                        let feedback = process_attack(defender_board, selector_position);
-
-                        if feedback.valid_attack {
-                            match feedback.tile_at_attack {
-                                Tile::Unknown => defender_board
-                                    .place_marker_on_board(selector_position, Tile::Miss),
-                                Tile::Ship(_) => defender_board
-                                    .place_marker_on_board(selector_position, Tile::Hit),
-                                _ => defender_board
-                                    .place_marker_on_board(selector_position, Tile::Miss),
-                            }
-
-                            refresh_display(refresh_amount as u16);
-                            println!("{}'s board", other_player);
-                            display_game_board(defender_board, true);
-                            println!("Your board");
-                            display_game_board(attacker_board, false);
-
-                            turn_feedback.tile_at_attack = feedback.tile_at_attack;
-
-                            if feedback.sunk_a_ship {
-                                turn_feedback.sunk_a_ship = true;
-                            }
-
-                            if feedback.won_the_game {
-                                turn_feedback.won_the_game = true;
-                            }
-
-                            turn_feedback.valid_attack = true;
-
-                            break;
-                        } else {
+                        return None;
+                    }
+                    KeyCode::Up => {
+                        move_selector_position(selector_position, Movement::Up, 0, board_size)
+                    }
+                    KeyCode::Down => {
+                        move_selector_position(selector_position, Movement::Down, 0, board_size)
+                    }
+                    KeyCode::Left => {
+                        move_selector_position(selector_position, Movement::Left, 0, board_size)
+                    }
+                    KeyCode::Right => {
+                        move_selector_position(selector_position, Movement::Right, 0, board_size)
+                    }
+                    KeyCode::Enter => {
+                        if matches!(
+                            defender_board.get_tile_at_position(selector_position),
+                            Tile::Hit | Tile::Miss
+                        ) {
                             selector_position
+                        } else {
+                            terminal::disable_raw_mode().expect("Failed to disable raw mode");
+                            refresh_display(refresh_amount as u16);
+                            return Some(selector_position);
                         }
                     }
                     _ => selector_position,
@@ -126,8 +135,4 @@ pub fn player_turn(
         terminal::disable_raw_mode().expect("Failed to disable raw mode");
         refresh_display(refresh_amount as u16);
     }
-
-    turn_feedback.defender_board = defender_board;
-
-    turn_feedback
 }
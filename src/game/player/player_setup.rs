@@ -1,118 +1,104 @@
 use crate::display::game::display_game_board;
 use crate::display::inputs::OptionSelect;
+use crate::game::fleet::FleetDefinition;
 use crate::game::place_ship_on_board;
-use crate::utils::{
-    ships::get_ship,
-    terminal::{move_selector_position, refresh_display, Movement},
-};
-use crate::{GameBoard, Player, Position, ShipOrientation, ShipType, GRID_SIZE};
+use crate::utils::terminal::{enable_raw_mode, move_selector_position, refresh_display, Movement};
+use crate::{BoardSize, GameBoard, Player, Position, Ship, ShipId, ShipOrientation};
 use crossterm::{
     event::{read, Event, KeyCode, KeyEvent, KeyEventKind},
     terminal,
 };
 
-pub fn player_setup(player: Player) -> GameBoard {
-    let mut board = GameBoard::new();
-
-    let mut ship_names = [
-        "Carrier".to_string(),
-        "Battleship".to_string(),
-        "Cruiser".to_string(),
-        "Submarine".to_string(),
-        "Destroyer".to_string(),
-    ]
-    .to_vec();
-
-    while ship_names.len() > 0 {
-        let option_template = OptionSelect::new()
-            .set_title(&format!(
-                "{}, Select a ship to place",
-                player.get_player_name()
-            ))
-            .add_option_if_true(
-                "Carrier".to_string(),
-                ship_names.contains(&"Carrier".to_string()),
-            )
-            .add_option_if_true(
-                "Battleship".to_string(),
-                ship_names.contains(&"Battleship".to_string()),
-            )
-            .add_option_if_true(
-                "Cruiser".to_string(),
-                ship_names.contains(&"Cruiser".to_string()),
-            )
-            .add_option_if_true(
-                "Submarine".to_string(),
-                ship_names.contains(&"Submarine".to_string()),
-            )
-            .add_option_if_true(
-                "Destroyer".to_string(),
-                ship_names.contains(&"Destroyer".to_string()),
-            )
-            .ask();
-
-        let additional_lines = 11;
+pub fn player_setup(player: Player, board_size: BoardSize, fleet: &FleetDefinition) -> GameBoard {
+    let mut board = GameBoard::new(board_size, fleet.clone());
+    // the board and its number header
+    let board_lines = board_size.get_height() as u16 + 1;
+
+    let mut remaining_ships: Vec<u8> = (0..fleet.ships.len() as u8).collect();
+
+    while !remaining_ships.is_empty() {
+        let ship_labels: Vec<String> = remaining_ships
+            .iter()
+            .map(|fleet_index| fleet.get_ship_label(*fleet_index))
+            .collect();
+
+        let mut option_template = OptionSelect::new().set_title(&format!(
+            "{}, Select a ship to place",
+            player.get_player_name()
+        ));
+        for ship_label in ship_labels.iter() {
+            option_template = option_template.add_option(ship_label);
+        }
+        let selected_ship = option_template.ask();
 
         // also clears game board
-        if ship_names.len() != 5 {
-            refresh_display(ship_names.len() as u16 + 1 + additional_lines);
+        if remaining_ships.len() != fleet.ships.len() {
+            refresh_display(remaining_ships.len() as u16 + 1 + board_lines);
         } else {
-            refresh_display(ship_names.len() as u16 + 1);
+            refresh_display(remaining_ships.len() as u16 + 1);
         }
 
-        let ship_type = match option_template.as_str() {
-            "Carrier" => {
-                ship_names.retain(|x| x != &"Carrier".to_string());
-                ShipType::CarrierHorizontal
-            }
-            "Battleship" => {
-                ship_names.retain(|x| x != &"Battleship".to_string());
-                ShipType::BattleshipHorizontal
-            }
-            "Cruiser" => {
-                ship_names.retain(|x| x != &"Cruiser".to_string());
-                ShipType::CruiserHorizontal
-            }
-            "Submarine" => {
-                ship_names.retain(|x| x != &"Submarine".to_string());
-                ShipType::SubmarineHorizontal
-            }
-            "Destroyer" => {
-                ship_names.retain(|x| x != &"Destroyer".to_string());
-                ShipType::DestroyerHorizontal
-            }
-            _ => panic!("Invalid ship type"),
-        };
+        let selected_index = ship_labels
+            .iter()
+            .position(|ship_label| *ship_label == selected_ship)
+            .unwrap_or_else(|| panic!("Invalid ship type"));
+        let fleet_index = remaining_ships.remove(selected_index);
+
+        let ship_id = ShipId(fleet_index);
+        let ship_length = fleet.get_ship_class(fleet_index).length;
+
+        // the ships placed so far can leave no room for the rest, then the fleet is placed again
+        if !board.has_room_for(ship_length) {
+            println!(
+                "There is no room left for the {}, place your ships again",
+                fleet.get_ship_label(fleet_index)
+            );
+            board = GameBoard::new(board_size, fleet.clone());
+            remaining_ships = (0..fleet.ships.len() as u8).collect();
+            continue;
+        }
 
-        ship_placement_selection(&mut board, ship_type);
+        ship_placement_selection(&mut board, ship_id, ship_length);
     }
-    refresh_display(11);
+    refresh_display(board_lines);
 
     board
 }
 
-fn ship_placement_selection(board: &mut GameBoard, ship: ShipType) {
-    let mut ship = get_ship(ship);
-    let ship_length = ship.ship_type.get_ship_length();
-
-    let mut selector_position = Position::new(4, 4 - calculate_ship_center(ship_length as i8));
+fn ship_placement_selection(board: &mut GameBoard, ship_id: ShipId, ship_length: u8) {
+    let board_size = board.get_size();
+    // ships that are longer than the board is wide start out standing up
+    let orientation = if ship_length as i8 > board_size.get_width() {
+        ShipOrientation::Vertical
+    } else {
+        ShipOrientation::Horizontal
+    };
+    let mut ship = Ship::new(ship_id, ship_length, orientation);
+
+    let center = board_size.get_center();
+    let mut selector_position = match orientation {
+        ShipOrientation::Horizontal => Position::new(
+            center.get_y(),
+            (center.get_x() - calculate_ship_center(ship_length as i8)).max(0),
+        ),
+        ShipOrientation::Vertical => Position::new(0, center.get_x()),
+    };
 
     // + 1 on refresh screen to account for label line on game board
     loop {
-        let board_with_ship = GameBoard::set(
-            place_ship_on_board(
-                board.board,
-                &ship,
-                selector_position.get_y() as usize,
-                selector_position.get_x() as usize,
-                true,
-            )
-            .1,
-        );
-
-        display_game_board(board_with_ship, false);
-
-        terminal::enable_raw_mode().expect("Failed to enable raw mode");
+        let mut board_with_ship = board.clone();
+        board_with_ship.board = place_ship_on_board(
+            board.board.clone(),
+            &ship,
+            selector_position.get_y() as usize,
+            selector_position.get_x() as usize,
+            true,
+        )
+        .1;
+
+        display_game_board(&board_with_ship, false);
+
+        enable_raw_mode();
         selector_position = if let Ok(event) = read() {
             match event {
                 Event::Key(KeyEvent {
@@ -126,15 +112,20 @@ fn ship_placement_selection(board: &mut GameBoard, ship: ShipType) {
                         std::process::exit(0);
                     }
                     KeyCode::Up => {
-                        let cycle_offset = match ship.orientation {
+                        let cycle_offset = match ship.get_orientation() {
                             ShipOrientation::Horizontal => 0,
                             ShipOrientation::Vertical => ship_length - 1,
                         };
 
-                        move_selector_position(selector_position, Movement::Up, cycle_offset as i8)
+                        move_selector_position(
+                            selector_position,
+                            Movement::Up,
+                            cycle_offset as i8,
+                            board_size,
+                        )
                     }
                     KeyCode::Down => {
-                        let cycle_offset = match ship.orientation {
+                        let cycle_offset = match ship.get_orientation() {
                             ShipOrientation::Horizontal => 0,
                             ShipOrientation::Vertical => ship_length - 1,
                         };
@@ -143,10 +134,11 @@ fn ship_placement_selection(board: &mut GameBoard, ship: ShipType) {
                             selector_position,
                             Movement::Down,
                             cycle_offset as i8,
+                            board_size,
                         )
                     }
                     KeyCode::Left => {
-                        let cycle_offset = match ship.orientation {
+                        let cycle_offset = match ship.get_orientation() {
                             ShipOrientation::Horizontal => ship_length - 1,
                             ShipOrientation::Vertical => 0,
                         };
@@ -155,10 +147,11 @@ fn ship_placement_selection(board: &mut GameBoard, ship: ShipType) {
                             selector_position,
                             Movement::Left,
                             cycle_offset as i8,
+                            board_size,
                         )
                     }
                     KeyCode::Right => {
-                        let cycle_offset = match ship.orientation {
+                        let cycle_offset = match ship.get_orientation() {
                             ShipOrientation::Horizontal => ship_length - 1,
                             ShipOrientation::Vertical => 0,
                         };
@@ -167,58 +160,49 @@ fn ship_placement_selection(board: &mut GameBoard, ship: ShipType) {
                             selector_position,
                             Movement::Right,
                             cycle_offset as i8,
+                            board_size,
                         )
                     }
                     KeyCode::Char('r') => {
-                        let mut x = selector_position.get_x();
-                        let mut y = selector_position.get_y();
-                        let transform_amount = calculate_ship_center(ship_length as i8);
-
-                        match ship.orientation {
-                            ShipOrientation::Horizontal => {
-                                y -= transform_amount;
-                                x += transform_amount;
-                            }
-                            ShipOrientation::Vertical => {
-                                y += transform_amount;
-                                x -= transform_amount;
-                            }
-                        }
-
-                        // ensure ship stays on screen
-                        if x < 0 {
-                            x = 0;
-                        } else if x + ship_length as i8 > GRID_SIZE {
-                            x = GRID_SIZE - ship_length as i8;
-                        }
+                        // the space the ship takes up once it has been rotated
+                        let (rotated_width, rotated_height) = match ship.get_orientation() {
+                            ShipOrientation::Horizontal => (1, ship_length as i8),
+                            ShipOrientation::Vertical => (ship_length as i8, 1),
+                        };
 
-                        if y < 0 {
-                            y = 0;
-                        } else if y + ship_length as i8 > GRID_SIZE {
-                            y = GRID_SIZE - ship_length as i8;
-                        }
+                        // a ship longer than the board is wide or high can only lie the other way
+                        if rotated_width > board_size.get_width()
+                            || rotated_height > board_size.get_height()
+                        {
+                            selector_position
+                        } else {
+                            let mut x = selector_position.get_x();
+                            let mut y = selector_position.get_y();
+                            let transform_amount = calculate_ship_center(ship_length as i8);
+
+                            match ship.get_orientation() {
+                                ShipOrientation::Horizontal => {
+                                    y -= transform_amount;
+                                    x += transform_amount;
+                                }
+                                ShipOrientation::Vertical => {
+                                    y += transform_amount;
+                                    x -= transform_amount;
+                                }
+                            }
 
-                        ship.orientation = match ship.orientation {
-                            ShipOrientation::Horizontal => ShipOrientation::Vertical,
-                            ShipOrientation::Vertical => ShipOrientation::Horizontal,
-                        };
+                            // ensure ship stays on screen
+                            x = x.clamp(0, board_size.get_width() - rotated_width);
+                            y = y.clamp(0, board_size.get_height() - rotated_height);
 
-                        ship.ship_type = ship.ship_type.get_opposite_ship_type();
+                            ship.rotate();
 
-                        Position::new(y, x)
+                            Position::new(y, x)
+                        }
                     }
                     KeyCode::Enter => {
                         terminal::disable_raw_mode().expect("Failed to disable raw mode");
-                        let (valid, new_board) = place_ship_on_board(
-                            board.board,
-                            &ship,
-                            selector_position.get_y() as usize,
-                            selector_position.get_x() as usize,
-                            false,
-                        );
-
-                        if valid {
-                            board.board = new_board;
+                        if board.place_ship(ship, selector_position).is_ok() {
                             break;
                         } else {
                             selector_position
@@ -233,7 +217,7 @@ fn ship_placement_selection(board: &mut GameBoard, ship: ShipType) {
         };
 
         terminal::disable_raw_mode().expect("Failed to disable raw mode");
-        refresh_display(GRID_SIZE as u16 + 1);
+        refresh_display(board_size.get_height() as u16 + 1);
     }
 }
 
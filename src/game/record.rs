@@ -0,0 +1,404 @@
+use crate::game::fleet::FleetDefinition;
+use crate::game::save::{
+    fleet_lines, format_position, parse_board_size, parse_placement, parse_player, parse_position,
+    parse_ship_class, place_saved_ships, placement_lines, player_to_string,
+};
+use crate::game::{process_attack, ShotOutcome};
+use crate::{GameBoard, Player, Position};
+use std::fs;
+
+const RECORD_FORMAT_HEADER: &str = "battleship-record 1";
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RecordedShot {
+    pub attacker: Player,
+    pub position: Position,
+    pub outcome: ShotOutcome,
+}
+
+// a finished or abandoned game, move by move, starting from both fleets in place
+#[derive(Debug, PartialEq, Clone)]
+pub struct GameRecord {
+    player_one_name: String,
+    player_two_name: String,
+    player_one_board: GameBoard,
+    player_two_board: GameBoard,
+    shots: Vec<RecordedShot>,
+}
+
+impl GameRecord {
+    pub fn new(
+        player_one_name: &str,
+        player_two_name: &str,
+        player_one_board: &GameBoard,
+        player_two_board: &GameBoard,
+    ) -> Self {
+        Self {
+            player_one_name: player_one_name.to_string(),
+            player_two_name: player_two_name.to_string(),
+            player_one_board: player_one_board.clone(),
+            player_two_board: player_two_board.clone(),
+            shots: Vec::new(),
+        }
+    }
+
+    pub fn get_player_name(&self, player: Player) -> &str {
+        match player {
+            Player::PlayerOne => &self.player_one_name,
+            Player::PlayerTwo => &self.player_two_name,
+        }
+    }
+
+    pub fn shots(&self) -> &[RecordedShot] {
+        &self.shots
+    }
+
+    pub fn add_shot(&mut self, attacker: Player, position: Position, outcome: ShotOutcome) {
+        self.shots.push(RecordedShot {
+            attacker,
+            position,
+            outcome,
+        });
+    }
+
+    pub fn get_winner(&self) -> Option<Player> {
+        match self.shots.last() {
+            Some(RecordedShot {
+                attacker,
+                outcome: ShotOutcome::Won(_),
+                ..
+            }) => Some(*attacker),
+            _ => None,
+        }
+    }
+
+    // both boards as they were after the given number of shots, player one's board first
+    pub fn get_boards_after(&self, shots_played: usize) -> (GameBoard, GameBoard) {
+        let mut player_one_board = self.player_one_board.clone();
+        let mut player_two_board = self.player_two_board.clone();
+
+        for shot in self.shots.iter().take(shots_played) {
+            let defender_board = match shot.attacker {
+                Player::PlayerOne => &mut player_two_board,
+                Player::PlayerTwo => &mut player_one_board,
+            };
+            process_attack(defender_board, shot.position);
+        }
+
+        (player_one_board, player_two_board)
+    }
+
+    pub fn to_record_string(&self) -> String {
+        let board_size = self.player_one_board.get_size();
+        let mut lines = vec![
+            RECORD_FORMAT_HEADER.to_string(),
+            format!("player one {}", self.player_one_name),
+            format!("player two {}", self.player_two_name),
+            format!(
+                "size {}x{}",
+                board_size.get_width(),
+                board_size.get_height()
+            ),
+        ];
+        lines.extend(fleet_lines(self.player_one_board.get_fleet_definition()));
+
+        lines.push("board one".to_string());
+        lines.extend(placement_lines(&self.player_one_board));
+        lines.push("board two".to_string());
+        lines.extend(placement_lines(&self.player_two_board));
+
+        for shot in self.shots.iter() {
+            lines.push(format!(
+                "shot {} {} {}",
+                player_to_string(&shot.attacker),
+                format_position(shot.position),
+                format_shot_outcome(&shot.outcome)
+            ));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    // every shot is replayed against the recorded fleets, so a record can not contradict itself
+    pub fn parse(record: &str) -> Result<Self, String> {
+        let mut lines = record.lines();
+        if lines.next() != Some(RECORD_FORMAT_HEADER) {
+            return Err("Not a battleship record".to_string());
+        }
+
+        let mut player_names = [String::new(), String::new()];
+        let mut board_size = None;
+        let mut fleet_name = String::new();
+        let mut ship_classes = Vec::new();
+        let mut placements = [Vec::new(), Vec::new()];
+        let mut current_board = None;
+        let mut shots = Vec::new();
+
+        for line in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let invalid = || format!("Invalid line in record \"{}\"", line);
+
+            match key {
+                "player" => match value.split_once(' ') {
+                    Some(("one", name)) => player_names[0] = name.to_string(),
+                    Some(("two", name)) => player_names[1] = name.to_string(),
+                    _ => return Err(invalid()),
+                },
+                "size" => board_size = Some(parse_board_size(value).ok_or_else(invalid)?),
+                "fleet" => fleet_name = value.to_string(),
+                "ship" => ship_classes.push(parse_ship_class(value).ok_or_else(invalid)?),
+                "board" => {
+                    current_board = match value {
+                        "one" => Some(0),
+                        "two" => Some(1),
+                        _ => return Err(invalid()),
+                    }
+                }
+                "placed" => {
+                    let board = current_board.ok_or_else(invalid)?;
+                    placements[board].push(parse_placement(value).ok_or_else(invalid)?);
+                }
+                "shot" => shots.push(parse_shot(value).ok_or_else(invalid)?),
+                _ => return Err(invalid()),
+            }
+        }
+
+        let board_size = board_size.ok_or("The record has no board size")?;
+        let fleet = FleetDefinition::new(&fleet_name, ship_classes);
+        if !fleet.fits_on(board_size) {
+            return Err("The recorded fleet does not fit on the recorded board".to_string());
+        }
+
+        let [placements_one, placements_two] = placements;
+        let [player_one_name, player_two_name] = player_names;
+        let mut game_record = Self {
+            player_one_name,
+            player_two_name,
+            player_one_board: place_saved_ships(board_size, &fleet, placements_one)?,
+            player_two_board: place_saved_ships(board_size, &fleet, placements_two)?,
+            shots: Vec::new(),
+        };
+
+        let (mut player_one_board, mut player_two_board) = game_record.get_boards_after(0);
+        for (shot_number, shot) in shots.into_iter().enumerate() {
+            let defender_board = match shot.attacker {
+                Player::PlayerOne => &mut player_two_board,
+                Player::PlayerTwo => &mut player_one_board,
+            };
+
+            let outcome = process_attack(defender_board, shot.position).get_shot_outcome();
+            if outcome.as_ref() != Some(&shot.outcome) {
+                return Err(format!(
+                    "Shot {} does not match the recorded fleets",
+                    shot_number + 1
+                ));
+            }
+
+            game_record.shots.push(shot);
+        }
+
+        Ok(game_record)
+    }
+}
+
+// "player-one 3,4 sunk 2 Destroyer"
+fn parse_shot(value: &str) -> Option<RecordedShot> {
+    let mut parts = value.splitn(3, ' ');
+
+    Some(RecordedShot {
+        attacker: parse_player(parts.next()?)?,
+        position: parse_position(parts.next()?)?,
+        outcome: parse_shot_outcome(parts.next()?)?,
+    })
+}
+
+// "miss", "hit", or the sunk ship as "sunk 2 Destroyer" and "won 2 Destroyer"
+pub fn format_shot_outcome(outcome: &ShotOutcome) -> String {
+    match outcome {
+        ShotOutcome::Miss => "miss".to_string(),
+        ShotOutcome::Hit => "hit".to_string(),
+        ShotOutcome::Sunk(ship) => format!("sunk {} {}", ship.length, ship.name),
+        ShotOutcome::Won(ship) => format!("won {} {}", ship.length, ship.name),
+    }
+}
+
+pub fn parse_shot_outcome(value: &str) -> Option<ShotOutcome> {
+    let (outcome, ship) = match value.split_once(' ') {
+        Some((outcome, ship)) => (outcome, Some(ship)),
+        None => (value, None),
+    };
+
+    match (outcome, ship) {
+        ("miss", None) => Some(ShotOutcome::Miss),
+        ("hit", None) => Some(ShotOutcome::Hit),
+        ("sunk", Some(ship)) => Some(ShotOutcome::Sunk(parse_ship_class(ship)?)),
+        ("won", Some(ship)) => Some(ShotOutcome::Won(parse_ship_class(ship)?)),
+        _ => None,
+    }
+}
+
+// any number of games can share one file, each one starts with its own header
+pub fn save_records(path: &str, game_records: &[GameRecord]) -> Result<(), String> {
+    let records: Vec<String> = game_records
+        .iter()
+        .map(|game_record| game_record.to_record_string())
+        .collect();
+
+    fs::write(path, records.join("\n"))
+        .map_err(|error| format!("Could not write {}: {}", path, error))
+}
+
+pub fn load_records(path: &str) -> Result<Vec<GameRecord>, String> {
+    let records =
+        fs::read_to_string(path).map_err(|error| format!("Could not read {}: {}", path, error))?;
+
+    let mut game_records = Vec::new();
+    let mut current_record = String::new();
+
+    for line in records.lines() {
+        if line == RECORD_FORMAT_HEADER && !current_record.is_empty() {
+            game_records.push(GameRecord::parse(&current_record)?);
+            current_record.clear();
+        }
+        current_record.push_str(line);
+        current_record.push('\n');
+    }
+
+    if !current_record.trim().is_empty() {
+        game_records.push(GameRecord::parse(&current_record)?);
+    }
+
+    if game_records.is_empty() {
+        return Err(format!("{} has no recorded games", path));
+    }
+
+    Ok(game_records)
+}
+
+// the record written when the game was saved, it has to end where the saved game stands
+pub fn load_saved_game_record(
+    path: &str,
+    player_one_board: &GameBoard,
+    player_two_board: &GameBoard,
+) -> Result<GameRecord, String> {
+    let game_record = match load_records(path)?.as_slice() {
+        [game_record] => game_record.clone(),
+        _ => return Err(format!("{} does not hold a single game", path)),
+    };
+
+    let (recorded_one, recorded_two) = game_record.get_boards_after(game_record.shots().len());
+    if recorded_one != *player_one_board || recorded_two != *player_two_board {
+        return Err(format!(
+            "The game record in {} does not match the saved game",
+            path
+        ));
+    }
+
+    Ok(game_record)
+}
+
+// the record path and record a resumed game carries on with, no path when it can not be continued
+pub fn resume_game_record(
+    record_path: Option<String>,
+    player_one_board: &GameBoard,
+    player_two_board: &GameBoard,
+) -> (Option<String>, Option<GameRecord>) {
+    let Some(path) = record_path else {
+        return (None, None);
+    };
+
+    match load_saved_game_record(&path, player_one_board, player_two_board) {
+        Ok(game_record) => (Some(path), Some(game_record)),
+        Err(error) => {
+            println!("{}, the rest of the game is not recorded", error);
+            (None, None)
+        }
+    }
+}
+
+// only writes when recording was asked for, and tells the player where the record went
+pub fn write_game_records(record_path: Option<&str>, game_records: &[GameRecord]) {
+    if let Some(path) = record_path {
+        match save_records(path, game_records) {
+            Ok(()) => println!("Game record written to {}", path),
+            Err(error) => println!("{}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::computer::computer_setup::computer_setup;
+    use crate::game::engine::Game;
+    use crate::utils::create_rng;
+    use crate::BoardSize;
+
+    // a game with a few shots played, and the record of those shots
+    fn recorded_game() -> (Game, GameRecord) {
+        let board_size = BoardSize::new(8, 8);
+        let fleet = FleetDefinition::russian();
+        let mut rng = create_rng(Some(5));
+        let mut game = Game::new(board_size, fleet.clone());
+        for player in [Player::PlayerOne, Player::PlayerTwo] {
+            let board = computer_setup(board_size, &fleet, &mut rng).unwrap();
+            game.place_fleet(player, board).unwrap();
+        }
+
+        let mut game_record = GameRecord::new(
+            "One",
+            "Two",
+            game.get_board(Player::PlayerOne),
+            game.get_board(Player::PlayerTwo),
+        );
+        for x in 0..4 {
+            let player = game.current_turn();
+            let position = Position::new(x, x);
+            let outcome = game.fire(player, position).unwrap();
+            game_record.add_shot(player, position, outcome);
+        }
+
+        (game, game_record)
+    }
+
+    #[test]
+    fn record_string_round_trips() {
+        let (_, game_record) = recorded_game();
+
+        assert_eq!(
+            GameRecord::parse(&game_record.to_record_string()),
+            Ok(game_record)
+        );
+    }
+
+    #[test]
+    fn saved_game_record_has_to_match_the_saved_game() {
+        let (mut game, game_record) = recorded_game();
+        let path =
+            std::env::temp_dir().join(format!("battleship-test-{}.record", std::process::id()));
+        let path = path.to_str().unwrap();
+        save_records(path, std::slice::from_ref(&game_record)).unwrap();
+
+        let resumed = load_saved_game_record(
+            path,
+            game.get_board(Player::PlayerOne),
+            game.get_board(Player::PlayerTwo),
+        );
+        assert_eq!(resumed, Ok(game_record));
+
+        let player = game.current_turn();
+        game.fire(player, Position::new(7, 0)).unwrap();
+        let resumed = load_saved_game_record(
+            path,
+            game.get_board(Player::PlayerOne),
+            game.get_board(Player::PlayerTwo),
+        );
+        assert!(resumed.is_err());
+
+        let _ = fs::remove_file(path);
+    }
+}
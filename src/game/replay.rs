@@ -0,0 +1,102 @@
+use crate::display::game::{display_game_board, get_position_label};
+use crate::display::inputs::NumberInput;
+use crate::game::record::{load_records, GameRecord};
+use crate::utils::terminal::{enable_raw_mode, refresh_display};
+use crate::Player;
+use crossterm::{
+    event::{read, Event, KeyCode, KeyEvent, KeyEventKind},
+    terminal,
+};
+
+pub fn replay_game(record_path: &str) {
+    let game_records = match load_records(record_path) {
+        Ok(game_records) => game_records,
+        Err(error) => {
+            println!("{}", error);
+            return;
+        }
+    };
+
+    let game_number = if game_records.len() > 1 {
+        let game_number = NumberInput::new()
+            .set_message(&format!(
+                "{} has {} games, which one should be replayed?",
+                record_path,
+                game_records.len()
+            ))
+            .set_min(1)
+            .set_max(game_records.len() as i32)
+            .ask();
+        println!();
+        game_number as usize
+    } else {
+        1
+    };
+
+    replay_viewer(&game_records[game_number - 1]);
+}
+
+fn replay_viewer(game_record: &GameRecord) {
+    let total_shots = game_record.shots().len();
+    let mut shots_played = 0;
+
+    let player_one_name = game_record.get_player_name(Player::PlayerOne);
+    let player_two_name = game_record.get_player_name(Player::PlayerTwo);
+
+    loop {
+        let (player_one_board, player_two_board) = game_record.get_boards_after(shots_played);
+
+        println!("{} vs {}", player_one_name, player_two_name);
+        println!("{}", describe_shot(game_record, shots_played));
+        println!("{}'s board", player_two_name);
+        display_game_board(&player_two_board, false);
+        println!("{}'s board", player_one_name);
+        display_game_board(&player_one_board, false);
+        println!("← → to step, Home/End to jump, 'q' to quit");
+
+        // 5 label lines, plus the number header of each board
+        let refresh_amount = player_one_board.board.len() + player_two_board.board.len() + 2 + 5;
+
+        enable_raw_mode();
+        if let Ok(Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        })) = read()
+        {
+            match code {
+                KeyCode::Char('q') => {
+                    terminal::disable_raw_mode().expect("Failed to disable raw mode");
+                    break;
+                }
+                KeyCode::Left => shots_played = shots_played.saturating_sub(1),
+                KeyCode::Right => shots_played = (shots_played + 1).min(total_shots),
+                KeyCode::Home => shots_played = 0,
+                KeyCode::End => shots_played = total_shots,
+                _ => {}
+            }
+        }
+
+        terminal::disable_raw_mode().expect("Failed to disable raw mode");
+        refresh_display(refresh_amount as u16);
+    }
+}
+
+fn describe_shot(game_record: &GameRecord, shots_played: usize) -> String {
+    let total_shots = game_record.shots().len();
+
+    if shots_played == 0 {
+        return format!("Start of the game, {} shots recorded", total_shots);
+    }
+
+    let shot = &game_record.shots()[shots_played - 1];
+
+    format!(
+        "Shot {} of {}: {} fired at {}, {}",
+        shots_played,
+        total_shots,
+        game_record.get_player_name(shot.attacker),
+        get_position_label(shot.position),
+        shot.outcome.get_outcome_description()
+    )
+}
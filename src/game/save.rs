@@ -0,0 +1,554 @@
+use crate::game::computer::computers::monte_carlo::MonteCarloSettings;
+use crate::game::fleet::{FleetDefinition, ShipClass};
+use crate::game::observed_board::{ObservedBoard, ObservedTile, SinkAnnouncement};
+use crate::game::process_attack;
+use crate::{
+    BoardSize, Difficulty, GameBoard, Player, Position, Ship, ShipId, ShipOrientation, Tile,
+};
+use std::fs;
+
+pub const SAVE_FILE_PATH: &str = "battleship.save";
+const SAVE_FORMAT_HEADER: &str = "battleship-save 1";
+
+pub type SavedPlacement = (ShipId, Position, ShipOrientation);
+
+// everything the computer needs to pick up where it left off
+pub struct SavedComputer {
+    pub difficulty: Difficulty,
+    pub enemy_view: ObservedBoard,
+    pub memory: String,
+}
+
+// a game that is in progress, in single player the computer owns board two
+pub struct GameSnapshot {
+    pub current_player: Player,
+    pub player_one_board: GameBoard,
+    pub player_two_board: GameBoard,
+    pub computer: Option<SavedComputer>,
+    // the record file of the game, so the resumed game keeps adding to it
+    pub record_path: Option<String>,
+}
+
+impl GameSnapshot {
+    pub fn is_singleplayer(&self) -> bool {
+        self.computer.is_some()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        fs::write(SAVE_FILE_PATH, self.to_save_string())
+            .map_err(|error| format!("Could not write {}: {}", SAVE_FILE_PATH, error))
+    }
+
+    pub fn load() -> Result<Self, String> {
+        let save = fs::read_to_string(SAVE_FILE_PATH)
+            .map_err(|error| format!("Could not read {}: {}", SAVE_FILE_PATH, error))?;
+
+        Self::parse(&save)
+    }
+
+    // called once the game has been resumed, so a finished game can not be resumed again, while a
+    // save that fails to resume is kept
+    pub fn remove_save() {
+        let _ = fs::remove_file(SAVE_FILE_PATH);
+    }
+
+    pub fn save_exists() -> bool {
+        fs::metadata(SAVE_FILE_PATH).is_ok()
+    }
+
+    pub fn to_save_string(&self) -> String {
+        let board_size = self.player_one_board.get_size();
+        let fleet = self.player_one_board.get_fleet_definition();
+        let mut lines = vec![
+            SAVE_FORMAT_HEADER.to_string(),
+            format!("turn {}", player_to_string(&self.current_player)),
+            format!(
+                "size {}x{}",
+                board_size.get_width(),
+                board_size.get_height()
+            ),
+        ];
+        if let Some(record_path) = &self.record_path {
+            lines.push(format!("record {}", record_path));
+        }
+
+        lines.extend(fleet_lines(fleet));
+
+        for (label, board) in [
+            ("one", &self.player_one_board),
+            ("two", &self.player_two_board),
+        ] {
+            lines.push(format!("board {}", label));
+            lines.extend(placement_lines(board));
+            for row in board.board.iter() {
+                let row: String = row
+                    .iter()
+                    .map(|tile| match tile {
+                        Tile::Hit => 'x',
+                        Tile::Miss => 'o',
+                        _ => '.',
+                    })
+                    .collect();
+                lines.push(format!("row {}", row));
+            }
+        }
+
+        if let Some(computer) = &self.computer {
+            lines.push(format!(
+                "computer {}",
+                difficulty_to_string(&computer.difficulty)
+            ));
+            for y in 0..board_size.get_height() {
+                let row: String = (0..board_size.get_width())
+                    .map(|x| {
+                        match computer
+                            .enemy_view
+                            .get_tile_at_position(Position::new(y, x))
+                        {
+                            ObservedTile::Hit => 'x',
+                            ObservedTile::Miss => 'o',
+                            ObservedTile::Unknown => '.',
+                        }
+                    })
+                    .collect();
+                lines.push(format!("view {}", row));
+            }
+            for sunk_ship in computer.enemy_view.sunk_ships() {
+                lines.push(format!(
+                    "sunk {} {} {}",
+                    format_position(sunk_ship.position),
+                    sunk_ship.ship_length,
+                    sunk_ship.ship_name
+                ));
+            }
+            for resolved_hit in computer.enemy_view.resolved_hits() {
+                lines.push(format!("resolved {}", format_position(*resolved_hit)));
+            }
+            lines.push(format!("memory {}", computer.memory));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    pub fn parse(save: &str) -> Result<Self, String> {
+        let mut lines = save.lines();
+        if lines.next() != Some(SAVE_FORMAT_HEADER) {
+            return Err("Not a battleship save file".to_string());
+        }
+
+        let mut current_player = None;
+        let mut record_path = None;
+        let mut board_size = None;
+        let mut fleet_name = String::new();
+        let mut ship_classes = Vec::new();
+        // placed ships and rows of board one and two
+        let mut boards: [(Vec<SavedPlacement>, Vec<String>); 2] = Default::default();
+        let mut current_board = None;
+
+        let mut difficulty = None;
+        let mut view_rows = Vec::new();
+        let mut sunk_ships = Vec::new();
+        let mut resolved_hits = Vec::new();
+        let mut memory = String::new();
+
+        for line in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let invalid = || format!("Invalid line in save file \"{}\"", line);
+            // the size comes before the computer's view, which has to stay on the board
+            let on_board = |position: Position| {
+                board_size.is_some_and(|board_size| position.is_on_board(board_size))
+            };
+
+            match key {
+                "turn" => current_player = Some(parse_player(value).ok_or_else(invalid)?),
+                "record" if !value.is_empty() => record_path = Some(value.to_string()),
+                "size" => board_size = Some(parse_board_size(value).ok_or_else(invalid)?),
+                "fleet" => fleet_name = value.to_string(),
+                "ship" => ship_classes.push(parse_ship_class(value).ok_or_else(invalid)?),
+                "board" => {
+                    current_board = match value {
+                        "one" => Some(0),
+                        "two" => Some(1),
+                        _ => return Err(invalid()),
+                    }
+                }
+                "placed" => {
+                    let board = current_board.ok_or_else(invalid)?;
+                    boards[board]
+                        .0
+                        .push(parse_placement(value).ok_or_else(invalid)?);
+                }
+                "row" => {
+                    let board = current_board.ok_or_else(invalid)?;
+                    boards[board].1.push(value.to_string());
+                }
+                "computer" => difficulty = Some(parse_difficulty(value).ok_or_else(invalid)?),
+                "view" => view_rows.push(value.to_string()),
+                "sunk" => {
+                    let parts: Vec<&str> = value.splitn(3, ' ').collect();
+                    if parts.len() != 3 {
+                        return Err(invalid());
+                    }
+                    sunk_ships.push(SinkAnnouncement {
+                        position: parse_position(parts[0])
+                            .filter(|position| on_board(*position))
+                            .ok_or_else(invalid)?,
+                        ship_length: parts[1].parse::<u8>().map_err(|_| invalid())?,
+                        ship_name: parts[2].to_string(),
+                    });
+                }
+                "resolved" => resolved_hits.push(
+                    parse_position(value)
+                        .filter(|position| on_board(*position))
+                        .ok_or_else(invalid)?,
+                ),
+                "memory" => memory = value.to_string(),
+                _ => return Err(invalid()),
+            }
+        }
+
+        let current_player = current_player.ok_or("The save file has no turn")?;
+        let board_size = board_size.ok_or("The save file has no board size")?;
+        let fleet = FleetDefinition::new(&fleet_name, ship_classes);
+        if !fleet.fits_on(board_size) {
+            return Err("The saved fleet does not fit on the saved board".to_string());
+        }
+
+        let [(placed_one, rows_one), (placed_two, rows_two)] = boards;
+        let player_one_board = restore_board(board_size, &fleet, placed_one, rows_one)?;
+        let player_two_board = restore_board(board_size, &fleet, placed_two, rows_two)?;
+
+        let computer = match difficulty {
+            Some(difficulty) => {
+                let tiles = parse_rows(board_size, &view_rows)?
+                    .into_iter()
+                    .map(|row| {
+                        row.into_iter()
+                            .map(|marker| match marker {
+                                Tile::Hit => ObservedTile::Hit,
+                                Tile::Miss => ObservedTile::Miss,
+                                _ => ObservedTile::Unknown,
+                            })
+                            .collect()
+                    })
+                    .collect();
+
+                Some(SavedComputer {
+                    difficulty,
+                    enemy_view: ObservedBoard::restore(
+                        board_size,
+                        fleet,
+                        tiles,
+                        sunk_ships,
+                        resolved_hits,
+                    ),
+                    memory,
+                })
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            current_player,
+            player_one_board,
+            player_two_board,
+            computer,
+            record_path,
+        })
+    }
+}
+
+// places the saved ships and replays every marker, which also rebuilds the hit state of the fleet
+fn restore_board(
+    board_size: BoardSize,
+    fleet: &FleetDefinition,
+    placed_ships: Vec<SavedPlacement>,
+    rows: Vec<String>,
+) -> Result<GameBoard, String> {
+    let mut board = place_saved_ships(board_size, fleet, placed_ships)?;
+
+    for (y, row) in parse_rows(board_size, &rows)?.into_iter().enumerate() {
+        for (x, marker) in row.into_iter().enumerate() {
+            if marker == Tile::Unknown {
+                continue;
+            }
+
+            let feedback = process_attack(&mut board, Position::new(y as i8, x as i8));
+            if feedback.hit_a_ship != (marker == Tile::Hit) {
+                return Err("The saved markers do not match the saved ships".to_string());
+            }
+        }
+    }
+
+    Ok(board)
+}
+
+// a board with every ship of the fleet in place and no attacks on it yet
+pub fn place_saved_ships(
+    board_size: BoardSize,
+    fleet: &FleetDefinition,
+    placed_ships: Vec<SavedPlacement>,
+) -> Result<GameBoard, String> {
+    let mut board = GameBoard::new(board_size, fleet.clone());
+
+    for (id, position, orientation) in placed_ships {
+        let ship_class = fleet
+            .ships
+            .get(id.0 as usize)
+            .ok_or("A saved ship is not part of the fleet")?;
+        let ship = Ship::new(id, ship_class.length, orientation);
+        board
+            .place_ship(ship, position)
+            .map_err(|error| format!("A saved ship can not be placed: {}", error))?;
+    }
+
+    if !board.get_fleet().all_ships_placed() {
+        return Err("Some ships of the fleet are missing".to_string());
+    }
+
+    Ok(board)
+}
+
+pub fn fleet_lines(fleet: &FleetDefinition) -> Vec<String> {
+    let mut lines = vec![format!("fleet {}", fleet.name)];
+    for ship_class in fleet.ships.iter() {
+        lines.push(format!("ship {} {}", ship_class.length, ship_class.name));
+    }
+
+    lines
+}
+
+pub fn placement_lines(board: &GameBoard) -> Vec<String> {
+    board
+        .get_fleet()
+        .placed_ships()
+        .map(|placed_ship| {
+            format!(
+                "placed {} {} {}",
+                placed_ship.ship.get_id().0,
+                format_position(placed_ship.position),
+                orientation_to_string(placed_ship.ship.get_orientation())
+            )
+        })
+        .collect()
+}
+
+// "5 Carrier", the name is last so it may contain spaces, a ship has to be at least 1 long
+pub fn parse_ship_class(value: &str) -> Option<ShipClass> {
+    let (length, name) = value.split_once(' ')?;
+    let length = length.parse().ok().filter(|length| *length > 0)?;
+
+    Some(ShipClass::new(name, length))
+}
+
+// "0 3,4 horizontal"
+pub fn parse_placement(value: &str) -> Option<SavedPlacement> {
+    let parts: Vec<&str> = value.split(' ').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    Some((
+        ShipId(parts[0].parse().ok()?),
+        parse_position(parts[1])?,
+        parse_orientation(parts[2])?,
+    ))
+}
+
+fn parse_rows(board_size: BoardSize, rows: &[String]) -> Result<Vec<Vec<Tile>>, String> {
+    if rows.len() != board_size.get_height() as usize {
+        return Err("The save file has the wrong number of rows".to_string());
+    }
+
+    rows.iter()
+        .map(|row| {
+            let markers = row
+                .chars()
+                .map(|marker| match marker {
+                    'x' => Ok(Tile::Hit),
+                    'o' => Ok(Tile::Miss),
+                    '.' => Ok(Tile::Unknown),
+                    _ => Err(format!("Invalid marker \"{}\" in save file", marker)),
+                })
+                .collect::<Result<Vec<Tile>, String>>()?;
+
+            if markers.len() != board_size.get_width() as usize {
+                return Err("The save file has a row of the wrong length".to_string());
+            }
+
+            Ok(markers)
+        })
+        .collect()
+}
+
+pub fn format_position(position: Position) -> String {
+    format!("{},{}", position.get_y(), position.get_x())
+}
+
+pub fn parse_position(value: &str) -> Option<Position> {
+    let (y, x) = value.split_once(',')?;
+    Some(Position::new(y.parse().ok()?, x.parse().ok()?))
+}
+
+pub fn parse_board_size(value: &str) -> Option<BoardSize> {
+    let (width, height) = value.split_once('x')?;
+    let board_size = BoardSize::new(width.parse().ok()?, height.parse().ok()?);
+
+    if board_size.is_valid() {
+        Some(board_size)
+    } else {
+        None
+    }
+}
+
+pub fn player_to_string(player: &Player) -> &'static str {
+    match player {
+        Player::PlayerOne => "player-one",
+        Player::PlayerTwo => "player-two",
+    }
+}
+
+pub fn parse_player(value: &str) -> Option<Player> {
+    match value {
+        "player-one" => Some(Player::PlayerOne),
+        "player-two" => Some(Player::PlayerTwo),
+        _ => None,
+    }
+}
+
+fn difficulty_to_string(difficulty: &Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Easy => "easy",
+        Difficulty::Medium => "medium",
+        Difficulty::Hard => "hard",
+        Difficulty::Expert(_) => "expert",
+        Difficulty::Adaptive => "adaptive",
+    }
+}
+
+fn parse_difficulty(value: &str) -> Option<Difficulty> {
+    match value {
+        "easy" => Some(Difficulty::Easy),
+        "medium" => Some(Difficulty::Medium),
+        "hard" => Some(Difficulty::Hard),
+        // the sampling settings are not saved, the ones given when resuming are used
+        "expert" => Some(Difficulty::Expert(MonteCarloSettings::default())),
+        "adaptive" => Some(Difficulty::Adaptive),
+        _ => None,
+    }
+}
+
+pub fn orientation_to_string(orientation: ShipOrientation) -> &'static str {
+    match orientation {
+        ShipOrientation::Horizontal => "horizontal",
+        ShipOrientation::Vertical => "vertical",
+    }
+}
+
+fn parse_orientation(value: &str) -> Option<ShipOrientation> {
+    match value {
+        "horizontal" => Some(ShipOrientation::Horizontal),
+        "vertical" => Some(ShipOrientation::Vertical),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::computer::computer_setup::computer_setup;
+    use crate::game::ShotOutcome;
+    use crate::utils::create_rng;
+
+    #[test]
+    fn parse_ship_class_reads_length_and_name() {
+        assert_eq!(
+            parse_ship_class("2 Patrol Boat"),
+            Some(ShipClass::new("Patrol Boat", 2))
+        );
+        assert_eq!(parse_ship_class("0 Ghost"), None);
+        assert_eq!(parse_ship_class("-1 Ghost"), None);
+        assert_eq!(parse_ship_class("Carrier"), None);
+    }
+
+    #[test]
+    fn save_string_round_trips() {
+        let board_size = BoardSize::new(8, 7);
+        let fleet = FleetDefinition::russian();
+        let mut rng = create_rng(Some(3));
+        let mut player_one_board = computer_setup(board_size, &fleet, &mut rng).unwrap();
+        let player_two_board = computer_setup(board_size, &fleet, &mut rng).unwrap();
+
+        for x in 0..8 {
+            process_attack(&mut player_one_board, Position::new(2, x));
+        }
+
+        let snapshot = GameSnapshot {
+            current_player: Player::PlayerTwo,
+            player_one_board,
+            player_two_board,
+            computer: None,
+            record_path: Some("saved game.record".to_string()),
+        };
+        let restored = GameSnapshot::parse(&snapshot.to_save_string()).unwrap();
+
+        assert_eq!(restored.current_player, Player::PlayerTwo);
+        assert_eq!(restored.player_one_board, snapshot.player_one_board);
+        assert_eq!(restored.player_two_board, snapshot.player_two_board);
+        assert_eq!(restored.record_path.as_deref(), Some("saved game.record"));
+        assert!(!restored.is_singleplayer());
+    }
+
+    #[test]
+    fn parse_rejects_fleets_with_empty_ships() {
+        let board_size = BoardSize::new(6, 6);
+        let fleet = FleetDefinition::parse("Test", "Sub:2").unwrap();
+        let board = computer_setup(board_size, &fleet, &mut create_rng(Some(1))).unwrap();
+        let snapshot = GameSnapshot {
+            current_player: Player::PlayerOne,
+            player_one_board: board.clone(),
+            player_two_board: board,
+            computer: None,
+            record_path: None,
+        };
+
+        let save = snapshot
+            .to_save_string()
+            .replace("ship 2 Sub", "ship 2 Sub\nship 0 Ghost");
+
+        assert!(GameSnapshot::parse(&save).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_computer_views_off_the_board() {
+        let board_size = BoardSize::new(6, 6);
+        let fleet = FleetDefinition::parse("Test", "Sub:2").unwrap();
+        let board = computer_setup(board_size, &fleet, &mut create_rng(Some(1))).unwrap();
+        let mut enemy_view = ObservedBoard::new(board_size, fleet);
+        enemy_view.record_attack(Position::new(0, 0), &ShotOutcome::Hit);
+        enemy_view.record_attack(
+            Position::new(0, 1),
+            &ShotOutcome::Sunk(ShipClass::new("Sub", 2)),
+        );
+        let snapshot = GameSnapshot {
+            current_player: Player::PlayerOne,
+            player_one_board: board.clone(),
+            player_two_board: board,
+            computer: Some(SavedComputer {
+                difficulty: Difficulty::Medium,
+                enemy_view,
+                memory: "-".to_string(),
+            }),
+            record_path: None,
+        };
+        let save = snapshot.to_save_string();
+
+        assert!(GameSnapshot::parse(&save).is_ok());
+        assert!(GameSnapshot::parse(&save.replace("sunk 0,1", "sunk 0,6")).is_err());
+        assert!(GameSnapshot::parse(&save.replace("resolved 0,0", "resolved 6,0")).is_err());
+    }
+}
@@ -1,12 +1,33 @@
 use crate::display::create_progress_bar;
 use crate::game::computer::computers::{
     hacker::Hacker, hunt_and_target::HuntAndTargetAttackStrategy,
-    probability_attack::ProbabilityAttackStrategy, random_attack::RandomAttackStrategy,
+    monte_carlo::MonteCarloAttackStrategy, probability_attack::ProbabilityAttackStrategy,
+    probability_density::ProbabilityDensityAttackStrategy, random_attack::RandomAttackStrategy,
 };
 use crate::game::computer::Computer;
-use crate::game::process_attack;
-use crate::{ComputerAttackStrategy, SimulationConfig, Tile};
-
+use crate::game::engine::{Game, GameError};
+use crate::game::export::write_simulation_export;
+use crate::game::fleet::FleetDefinition;
+use crate::game::record::{write_game_records, GameRecord};
+use crate::game::statistics::{
+    get_bucket_width, histogram, print_histogram, wilson_interval, Summary, CONFIDENCE_95,
+};
+use crate::game::ShotOutcome;
+use crate::utils::{create_rng, random_50_50, GameRng};
+use crate::{BoardSize, ComputerAttackStrategy, Player, SimulationConfig};
+use rand::Rng;
+use std::ops::Range;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::thread;
+use std::time::Duration;
+
+// how often the progress of the workers is reported while they play
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+// hit rates are percentages, shown in steps of 10%
+const HIT_RATE_BUCKET_WIDTH: f64 = 10.0;
+const GAME_LENGTH_BUCKETS: usize = 10;
+
+/// The outcome of every game of a simulation, see [`run_simulation`].
 pub struct SimulationResults {
     pub stats: Vec<SimulationResult>,
     pub computer_one_attack_strategy: ComputerAttackStrategy,
@@ -14,12 +35,15 @@ pub struct SimulationResults {
     pub computer_one_wins: i32,
     pub computer_two_wins: i32,
     pub games_played: i32,
+    // the seed the games were played with, running again with it gives the same games
+    pub seed: u64,
 }
 
 impl SimulationResults {
     pub fn new(
         computer_one_attack_strategy: ComputerAttackStrategy,
         computer_two_attack_strategy: ComputerAttackStrategy,
+        seed: u64,
     ) -> Self {
         Self {
             stats: Vec::new(),
@@ -28,6 +52,7 @@ impl SimulationResults {
             computer_one_wins: 0,
             computer_two_wins: 0,
             games_played: 0,
+            seed,
         }
     }
 
@@ -42,11 +67,19 @@ impl SimulationResults {
         self.games_played += 1;
     }
 
+    // combines the results of games played on another thread
+    pub fn merge(&mut self, other: SimulationResults) {
+        self.stats.extend(other.stats);
+        self.computer_one_wins += other.computer_one_wins;
+        self.computer_two_wins += other.computer_two_wins;
+        self.games_played += other.games_played;
+    }
+
     pub fn print_results(&self) {
-        println!("");
+        println!();
         println!("Simulation Results");
         println!("------------------");
-        println!("");
+        println!();
 
         println!(
             "Computer One Attack Strategy: {}",
@@ -56,14 +89,15 @@ impl SimulationResults {
             "Computer Two Attack Strategy: {}",
             self.computer_two_attack_strategy.get_attack_strategy_name()
         );
-        println!("");
+        println!();
 
         println!("Games Played: {}", self.games_played);
-        println!("");
+        println!("Seed: {}", self.seed);
+        println!();
 
         println!("Computer One Wins: {}", self.computer_one_wins);
         println!("Computer Two Wins: {}", self.computer_two_wins);
-        println!("");
+        println!();
 
         println!(
             "Computer One Win Percentage: {}%",
@@ -73,7 +107,150 @@ impl SimulationResults {
             "Computer Two Win Percentage: {}%",
             self.get_win_percentage(ComputerPlayer::ComputerTwo)
         );
-        println!("");
+        println!();
+
+        self.print_first_move_breakdown();
+        self.print_statistics();
+    }
+
+    fn print_statistics(&self) {
+        let computers = [
+            (ComputerPlayer::ComputerOne, "Computer One"),
+            (ComputerPlayer::ComputerTwo, "Computer Two"),
+        ];
+
+        println!("Win Rate, 95% Confidence Interval");
+        println!("---------------------------------");
+        println!();
+        for (computer, name) in computers {
+            let (low, high) = self.get_win_rate_interval(computer);
+            println!("{}: {:.2}% - {:.2}%", name, low * 100.0, high * 100.0);
+        }
+        println!();
+
+        println!("Shots to Win");
+        println!("------------");
+        println!();
+        for (computer, name) in computers {
+            let shots_to_win = self.get_shots_to_win(computer);
+
+            if shots_to_win.is_empty() {
+                println!("{}: no games won", name);
+            } else {
+                println!(
+                    "{}: {}",
+                    name,
+                    Summary::new(&shots_to_win).get_summary_line()
+                );
+            }
+        }
+        println!();
+
+        println!("Hit Rate per Game");
+        println!("-----------------");
+        for (computer, name) in computers {
+            let hit_rates = self.get_hit_rates(computer);
+
+            println!();
+            println!("{}: {}", name, Summary::new(&hit_rates).get_summary_line());
+            print_histogram(
+                &histogram(&hit_rates, 0.0, HIT_RATE_BUCKET_WIDTH),
+                HIT_RATE_BUCKET_WIDTH,
+                |start, end| format!("{}% - {}%", start, end),
+            );
+        }
+        println!();
+
+        let game_lengths = self.get_game_lengths();
+        let game_length_summary = Summary::new(&game_lengths);
+        let bucket_width = get_bucket_width(&game_length_summary, GAME_LENGTH_BUCKETS);
+
+        println!("Game Length in Shots");
+        println!("--------------------");
+        println!();
+        println!("{}", game_length_summary.get_summary_line());
+        print_histogram(
+            &histogram(&game_lengths, game_length_summary.min, bucket_width),
+            bucket_width,
+            |start, end| format!("{} - {}", start, end - 1.0),
+        );
+        println!();
+    }
+
+    /// The 95% Wilson confidence interval of the computer's win rate, as fractions.
+    pub fn get_win_rate_interval(&self, computer: ComputerPlayer) -> (f64, f64) {
+        let wins = self
+            .stats
+            .iter()
+            .filter(|result| result.winner == computer)
+            .count();
+
+        wilson_interval(wins, self.stats.len(), CONFIDENCE_95)
+    }
+
+    /// The shots the computer fired in each game it won.
+    pub fn get_shots_to_win(&self, computer: ComputerPlayer) -> Vec<f64> {
+        self.stats
+            .iter()
+            .filter(|result| result.winner == computer)
+            .map(|result| result.get_computer_stats(computer).shots_fired as f64)
+            .collect()
+    }
+
+    pub fn get_hit_rates(&self, computer: ComputerPlayer) -> Vec<f64> {
+        self.stats
+            .iter()
+            .map(|result| result.get_computer_stats(computer).get_hit_rate())
+            .collect()
+    }
+
+    pub fn get_game_lengths(&self) -> Vec<f64> {
+        self.stats
+            .iter()
+            .map(|result| result.get_game_length() as f64)
+            .collect()
+    }
+
+    fn print_first_move_breakdown(&self) {
+        println!("First Move Advantage");
+        println!("--------------------");
+        println!();
+
+        for (computer, name) in [
+            (ComputerPlayer::ComputerOne, "Computer One"),
+            (ComputerPlayer::ComputerTwo, "Computer Two"),
+        ] {
+            let games_started = self
+                .stats
+                .iter()
+                .filter(|result| result.starting_computer == computer)
+                .count();
+            let games_won = self
+                .stats
+                .iter()
+                .filter(|result| result.starting_computer == computer && result.winner == computer)
+                .count();
+
+            println!(
+                "{} Started: {} games, won {} ({}%)",
+                name,
+                games_started,
+                games_won,
+                get_percentage(games_won, games_started)
+            );
+        }
+
+        let starter_wins = self
+            .stats
+            .iter()
+            .filter(|result| result.starting_computer == result.winner)
+            .count();
+        println!(
+            "Games Won by the Starting Computer: {} ({}%)",
+            starter_wins,
+            get_percentage(starter_wins, self.stats.len())
+        );
+        println!();
     }
 
     fn get_win_percentage(&self, computer: ComputerPlayer) -> f32 {
@@ -88,10 +265,22 @@ impl SimulationResults {
     }
 }
 
+// none of the games is zero percent, rather than not a number
+fn get_percentage(count: usize, total: usize) -> f32 {
+    if total == 0 {
+        return 0.0;
+    }
+
+    (count as f32 / total as f32) * 100.0
+}
+
+/// How a single simulated game went for both computers.
 pub struct SimulationResult {
     pub computer_one_stats: ComputerStats,
     pub computer_two_stats: ComputerStats,
     pub winner: ComputerPlayer,
+    // the computer that fired the first shot
+    pub starting_computer: ComputerPlayer,
 }
 
 impl SimulationResult {
@@ -99,16 +288,30 @@ impl SimulationResult {
         computer_one_stats: ComputerStats,
         computer_two_stats: ComputerStats,
         winner: ComputerPlayer,
+        starting_computer: ComputerPlayer,
     ) -> Self {
         Self {
             computer_one_stats,
             computer_two_stats,
             winner,
+            starting_computer,
         }
     }
+
+    pub fn get_computer_stats(&self, computer: ComputerPlayer) -> &ComputerStats {
+        match computer {
+            ComputerPlayer::ComputerOne => &self.computer_one_stats,
+            ComputerPlayer::ComputerTwo => &self.computer_two_stats,
+        }
+    }
+
+    // the shots fired by both computers
+    pub fn get_game_length(&self) -> u32 {
+        self.computer_one_stats.shots_fired + self.computer_two_stats.shots_fired
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ComputerStats {
     pub hits: u32,
     pub misses: u32,
@@ -116,141 +319,364 @@ pub struct ComputerStats {
     pub shots_fired: u32,
 }
 
+impl ComputerStats {
+    // the percentage of shots that hit a ship
+    pub fn get_hit_rate(&self) -> f64 {
+        if self.shots_fired == 0 {
+            return 0.0;
+        }
+
+        self.hits as f64 / self.shots_fired as f64 * 100.0
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ComputerPlayer {
     ComputerOne,
     ComputerTwo,
 }
 
+impl ComputerPlayer {
+    // computer one plays as player one of the game
+    pub fn get_player(&self) -> Player {
+        match self {
+            ComputerPlayer::ComputerOne => Player::PlayerOne,
+            ComputerPlayer::ComputerTwo => Player::PlayerTwo,
+        }
+    }
+
+    // the name used for the computer in exported results
+    pub fn get_export_name(&self) -> &'static str {
+        match self {
+            ComputerPlayer::ComputerOne => "computer_one",
+            ComputerPlayer::ComputerTwo => "computer_two",
+        }
+    }
+}
+
 pub fn simulated_game(simulation_config: SimulationConfig) {
-    let mut computer_one =
-        match_attack_strategy_to_computer(&simulation_config.attack_strategy_one);
+    println!("Simulating Games...");
 
-    let mut computer_two =
-        match_attack_strategy_to_computer(&simulation_config.attack_strategy_two);
+    let (simulation_results, game_records) =
+        run_simulation(&simulation_config, create_progress_bar);
 
-    let games_to_simulate = simulation_config.games_to_play;
+    simulation_results.print_results();
+    write_game_records(simulation_config.record_path.as_deref(), &game_records);
+    write_simulation_export(
+        simulation_config.output_path.as_deref(),
+        &simulation_config,
+        &simulation_results,
+    );
+}
+
+/// Plays every game of the simulation without any terminal output, spread over one worker
+/// thread per CPU core.
+///
+/// `on_game_played` is called from the calling thread with the number of games played so far and
+/// the number of games to play. The games are only recorded when the config has a record path.
+pub fn run_simulation(
+    simulation_config: &SimulationConfig,
+    on_game_played: impl FnMut(i32, i32),
+) -> (SimulationResults, Vec<GameRecord>) {
+    let seed = simulation_config.seed.unwrap_or_else(rand::random);
 
     let mut simulation_results = SimulationResults::new(
         simulation_config.attack_strategy_one,
         simulation_config.attack_strategy_two,
+        seed,
     );
+    let mut game_records = Vec::new();
 
-    let mut games_played = 0;
-
-    println!("Simulating Games...");
-
-    while games_played < games_to_simulate {
-        computer_one.regenerate_computer_board();
-        computer_two.regenerate_computer_board();
+    let worker_results = play_in_parallel(
+        simulation_config.games_to_play,
+        on_game_played,
+        |games, games_played| simulate_games(simulation_config, seed, games, games_played),
+    );
 
-        let mut computer_one_board = computer_one.computer_board.clone();
-        let mut computer_two_board = computer_two.computer_board.clone();
+    for (worker_results, worker_records) in worker_results {
+        simulation_results.merge(worker_results);
+        game_records.extend(worker_records);
+    }
 
-        let mut computer_one_stats = ComputerStats {
-            hits: 0,
-            misses: 0,
-            ships_sunk: 0,
-            shots_fired: 0,
-        };
+    (simulation_results, game_records)
+}
 
-        let mut computer_two_stats = ComputerStats {
-            hits: 0,
-            misses: 0,
-            ships_sunk: 0,
-            shots_fired: 0,
-        };
+/// Splits the games into one contiguous range per CPU core and plays each range on its own worker
+/// thread, returning what every worker returned in game order.
+///
+/// `play_games` has to add one to the counter it is given after every game, `on_game_played` is
+/// called from the calling thread with the number of games played so far and the number of games
+/// to play.
+pub fn play_in_parallel<T: Send>(
+    games_to_play: i32,
+    mut on_game_played: impl FnMut(i32, i32),
+    play_games: impl Fn(Range<i32>, &AtomicI32) -> T + Sync,
+) -> Vec<T> {
+    let games_to_play = games_to_play.max(0);
+    let worker_count = thread::available_parallelism()
+        .map_or(1, |cores| cores.get())
+        .min(games_to_play.max(1) as usize);
+
+    let games_played = AtomicI32::new(0);
+    let play_games = &play_games;
+
+    let results = thread::scope(|scope| {
+        let mut first_game = 0;
+        let workers: Vec<_> = (0..worker_count)
+            .map(|worker| {
+                // the first workers take one more game when the games do not split evenly
+                let worker_games = games_to_play / worker_count as i32
+                    + i32::from((worker as i32) < games_to_play % worker_count as i32);
+                let games = first_game..first_game + worker_games;
+                first_game += worker_games;
+                let games_played = &games_played;
+
+                scope.spawn(move || play_games(games, games_played))
+            })
+            .collect();
+
+        while !workers.iter().all(|worker| worker.is_finished()) {
+            on_game_played(games_played.load(Ordering::Relaxed), games_to_play);
+            thread::sleep(PROGRESS_INTERVAL);
+        }
 
-        loop {
-            let computer_one_attack_position = computer_one
-                .attack_strategy
-                .calculate_best_attack(&computer_two_board);
-
-            let feedback = process_attack(computer_two_board, computer_one_attack_position);
-
-            if feedback.valid_attack {
-                match feedback.tile_at_attack {
-                    Tile::Unknown => computer_two_board
-                        .place_marker_on_board(computer_one_attack_position, Tile::Miss),
-                    Tile::Ship(_) => computer_two_board
-                        .place_marker_on_board(computer_one_attack_position, Tile::Hit),
-                    _ => computer_two_board
-                        .place_marker_on_board(computer_one_attack_position, Tile::Miss),
-                }
-            }
+        // every worker plays the games that follow the previous worker's, so joining them in order
+        // keeps the results in game order
+        workers
+            .into_iter()
+            .map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|_| panic!("a simulation worker panicked"))
+            })
+            .collect()
+    });
+
+    on_game_played(games_played.load(Ordering::Relaxed), games_to_play);
+
+    results
+}
 
-            if feedback.sunk_a_ship {
-                computer_one_stats.ships_sunk += 1;
-            }
+// every worker builds its own computers, so strategies and their random number generators are
+// never shared between threads
+fn simulate_games(
+    simulation_config: &SimulationConfig,
+    seed: u64,
+    games: Range<i32>,
+    total_games_played: &AtomicI32,
+) -> (SimulationResults, Vec<GameRecord>) {
+    let board_size = simulation_config.board_size;
+    let fleet = &simulation_config.fleet;
+
+    let mut computer_one = match_attack_strategy_to_computer(
+        &simulation_config.attack_strategy_one,
+        board_size,
+        fleet,
+        create_rng(Some(seed)),
+    )
+    .unwrap_or_else(|error| panic!("the simulated computer could not be set up: {}", error));
+
+    let mut computer_two = match_attack_strategy_to_computer(
+        &simulation_config.attack_strategy_two,
+        board_size,
+        fleet,
+        create_rng(Some(seed)),
+    )
+    .unwrap_or_else(|error| panic!("the simulated computer could not be set up: {}", error));
 
-            computer_one_stats.shots_fired += 1;
+    let mut simulation_results = SimulationResults::new(
+        simulation_config.attack_strategy_one,
+        simulation_config.attack_strategy_two,
+        seed,
+    );
 
-            if feedback.hit_a_ship {
-                computer_one_stats.hits += 1;
-            } else {
-                computer_one_stats.misses += 1;
-            }
+    let computer_one_name = format!(
+        "Computer One ({})",
+        simulation_results
+            .computer_one_attack_strategy
+            .get_attack_strategy_name()
+    );
+    let computer_two_name = format!(
+        "Computer Two ({})",
+        simulation_results
+            .computer_two_attack_strategy
+            .get_attack_strategy_name()
+    );
+    // only kept when the games should be written to a record file
+    let mut game_records = Vec::new();
+
+    for game_index in games {
+        // each game has its own generator, so it plays the same whichever worker plays it
+        let mut game_rng = create_rng(Some(get_game_seed(seed, game_index)));
+        computer_one.set_rng(create_rng(Some(game_rng.gen())));
+        computer_two.set_rng(create_rng(Some(game_rng.gen())));
+
+        if let Err(error) = computer_one
+            .start_new_game()
+            .and_then(|_| computer_two.start_new_game())
+        {
+            panic!("the simulated fleets could not be placed: {}", error);
+        }
 
-            if feedback.won_the_game {
-                break;
-            }
+        // the starting side is drawn from the game's own generator, so that the first move
+        // advantage evens out over a simulation without breaking the seed of each game
+        let starting_computer = if random_50_50(&mut game_rng) {
+            ComputerPlayer::ComputerOne
+        } else {
+            ComputerPlayer::ComputerTwo
+        };
 
-            let computer_two_attack_position = computer_two
-                .attack_strategy
-                .calculate_best_attack(&computer_one_board);
+        let mut game = Game::new(board_size, fleet.clone());
+        if let Err(error) = game
+            .place_fleet(Player::PlayerOne, computer_one.computer_board.clone())
+            .and_then(|_| game.place_fleet(Player::PlayerTwo, computer_two.computer_board.clone()))
+            .and_then(|_| game.set_first_turn(starting_computer.get_player()))
+        {
+            panic!("the simulated game could not be set up: {}", error);
+        }
 
-            let feedback = process_attack(computer_one_board, computer_two_attack_position);
+        let mut game_record = GameRecord::new(
+            &computer_one_name,
+            &computer_two_name,
+            game.get_board(Player::PlayerOne),
+            game.get_board(Player::PlayerTwo),
+        );
 
-            if feedback.valid_attack {
-                match feedback.tile_at_attack {
-                    Tile::Unknown => computer_one_board
-                        .place_marker_on_board(computer_two_attack_position, Tile::Miss),
-                    Tile::Ship(_) => computer_one_board
-                        .place_marker_on_board(computer_two_attack_position, Tile::Hit),
-                    _ => computer_one_board
-                        .place_marker_on_board(computer_two_attack_position, Tile::Miss),
+        let mut computer_one_stats = ComputerStats::default();
+        let mut computer_two_stats = ComputerStats::default();
+
+        while game.winner().is_none() {
+            let attacker = game.current_turn();
+            let (computer, computer_stats) = match attacker {
+                Player::PlayerOne => (&mut computer_one, &mut computer_one_stats),
+                Player::PlayerTwo => (&mut computer_two, &mut computer_two_stats),
+            };
+
+            let attack_position =
+                computer.calculate_attack(game.get_board(attacker.get_other_player()));
+
+            let shot_outcome = match game.fire(attacker, attack_position) {
+                Ok(shot_outcome) => shot_outcome,
+                Err(error) => panic!("a simulated computer made an invalid attack: {}", error),
+            };
+            computer.record_attack_result(attack_position, &shot_outcome);
+
+            computer_stats.shots_fired += 1;
+            match shot_outcome {
+                ShotOutcome::Miss => computer_stats.misses += 1,
+                ShotOutcome::Hit => computer_stats.hits += 1,
+                ShotOutcome::Sunk(_) | ShotOutcome::Won(_) => {
+                    computer_stats.hits += 1;
+                    computer_stats.ships_sunk += 1;
                 }
             }
 
-            if feedback.sunk_a_ship {
-                computer_two_stats.ships_sunk += 1;
-            }
-
-            computer_two_stats.shots_fired += 1;
-
-            if feedback.hit_a_ship {
-                computer_two_stats.hits += 1;
-            } else {
-                computer_two_stats.misses += 1;
-            }
-
-            if feedback.won_the_game {
-                break;
-            }
+            game_record.add_shot(attacker, attack_position, shot_outcome);
         }
 
-        let winner = if computer_one_stats.ships_sunk > computer_two_stats.ships_sunk {
-            ComputerPlayer::ComputerOne
-        } else {
-            ComputerPlayer::ComputerTwo
+        // the game only has a winner once a shot has sunk the last ship of the other fleet
+        let winner = match game.winner() {
+            Some(Player::PlayerOne) => ComputerPlayer::ComputerOne,
+            _ => ComputerPlayer::ComputerTwo,
         };
 
-        let simulation_result =
-            SimulationResult::new(computer_one_stats, computer_two_stats, winner);
+        let simulation_result = SimulationResult::new(
+            computer_one_stats,
+            computer_two_stats,
+            winner,
+            starting_computer,
+        );
 
         simulation_results.add_simulation_result(simulation_result);
 
-        games_played += 1;
-        create_progress_bar(games_played, games_to_simulate);
+        if simulation_config.record_path.is_some() {
+            game_records.push(game_record);
+        }
+
+        total_games_played.fetch_add(1, Ordering::Relaxed);
     }
 
-    simulation_results.print_results();
+    (simulation_results, game_records)
+}
+
+// the seed of game n is mixed from the seed of the whole run and n, so runs with nearby seeds do
+// not share games, as they would if the seed was simply counted up
+pub fn get_game_seed(seed: u64, game_index: i32) -> u64 {
+    split_mix(split_mix(seed).wrapping_add(game_index as u64))
+}
+
+// one step of the splitmix64 generator, which spreads nearby numbers over the whole range
+fn split_mix(value: u64) -> u64 {
+    let mut mixed = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    mixed ^ (mixed >> 31)
+}
+
+/// Fails when the fleet does not fit on the board, see [`Computer::new`].
+pub fn match_attack_strategy_to_computer(
+    attack_strategy: &ComputerAttackStrategy,
+    board_size: BoardSize,
+    fleet: &FleetDefinition,
+    rng: GameRng,
+) -> Result<Computer, GameError> {
+    let fleet = fleet.clone();
+    match attack_strategy {
+        ComputerAttackStrategy::Random => {
+            Computer::new(Box::new(RandomAttackStrategy), board_size, fleet, rng)
+        }
+        ComputerAttackStrategy::HuntAndTarget => Computer::new(
+            Box::new(HuntAndTargetAttackStrategy::new()),
+            board_size,
+            fleet,
+            rng,
+        ),
+        ComputerAttackStrategy::Probability => Computer::new(
+            Box::new(ProbabilityAttackStrategy::new()),
+            board_size,
+            fleet,
+            rng,
+        ),
+        ComputerAttackStrategy::ProbabilityDensity => Computer::new(
+            Box::new(ProbabilityDensityAttackStrategy),
+            board_size,
+            fleet,
+            rng,
+        ),
+        ComputerAttackStrategy::MonteCarlo(settings) => Computer::new(
+            Box::new(MonteCarloAttackStrategy::new(*settings)),
+            board_size,
+            fleet,
+            rng,
+        ),
+        ComputerAttackStrategy::Hacker => {
+            Computer::new_omniscient(Box::new(Hacker), board_size, fleet, rng)
+        }
+    }
 }
 
-fn match_attack_strategy_to_computer(attack_strategy: &ComputerAttackStrategy) -> Computer {
-    Computer::new(match attack_strategy {
-        ComputerAttackStrategy::Random => Box::new(RandomAttackStrategy),
-        ComputerAttackStrategy::HuntAndTarget => Box::new(HuntAndTargetAttackStrategy::new()),
-        ComputerAttackStrategy::Probability => Box::new(ProbabilityAttackStrategy::new()),
-        ComputerAttackStrategy::Hacker => Box::new(Hacker),
-    })
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearby_seeds_share_no_games() {
+        let games_of = |seed| {
+            (0..100)
+                .map(|game| get_game_seed(seed, game))
+                .collect::<Vec<u64>>()
+        };
+        let seed_one = games_of(1);
+        let seed_two = games_of(2);
+
+        assert_eq!(seed_one, games_of(1));
+        assert!(seed_one
+            .iter()
+            .all(|game_seed| !seed_two.contains(game_seed)));
+
+        let mut distinct = seed_one.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), seed_one.len());
+    }
 }
@@ -1,98 +1,236 @@
 use crate::display::inputs::Confirm;
 use crate::game::computer::computers::{
-    hunt_and_target::HuntAndTargetAttackStrategy, probability_attack::ProbabilityAttackStrategy,
+    adaptive::AdaptiveAttackStrategy, hunt_and_target::HuntAndTargetAttackStrategy,
+    monte_carlo::MonteCarloAttackStrategy, probability_density::ProbabilityDensityAttackStrategy,
     random_attack::RandomAttackStrategy,
 };
-use crate::game::computer::Computer;
-use crate::game::player::{player_setup::player_setup, player_turn};
-use crate::game::process_attack;
+use crate::game::computer::{AttackStrategy, Computer};
+use crate::game::engine::Game;
+use crate::game::fleet::FleetDefinition;
+use crate::game::heatmap::{record_placement, PlacementHeatmap};
+use crate::game::player::{player_setup::player_setup, player_turn, PlayerTurnResult};
+use crate::game::record::{resume_game_record, write_game_records, GameRecord};
+use crate::game::save::{GameSnapshot, SavedComputer};
+use crate::game::ShotOutcome;
+use crate::utils::create_rng;
 use crate::utils::terminal::refresh_display;
-use crate::{Difficulty, Player, Tile};
-
-pub fn singleplayer_game(difficulty: Difficulty) {
-    let mut player_one_board = player_setup(Player::PlayerOne);
-    let mut computer = match difficulty {
-        Difficulty::Easy => Computer::new(Box::new(RandomAttackStrategy)),
-        Difficulty::Medium => Computer::new(Box::new(HuntAndTargetAttackStrategy::new())),
-        Difficulty::Hard => Computer::new(Box::new(ProbabilityAttackStrategy::new())),
+use crate::{BoardSize, Difficulty, Player};
+
+pub fn singleplayer_game(
+    difficulty: Difficulty,
+    board_size: BoardSize,
+    fleet: FleetDefinition,
+    record_path: Option<String>,
+    seed: Option<u64>,
+    profile: String,
+) {
+    // the computer places its fleet first, so a fleet that does not fit is reported right away
+    let computer = Computer::new(
+        difficulty_attack_strategy(difficulty, &profile, board_size),
+        board_size,
+        fleet.clone(),
+        create_rng(seed),
+    )
+    .and_then(|mut computer| {
+        computer
+            .set_placement_strategy(difficulty.get_placement_strategy().get_placement_strategy())?;
+        Ok(computer)
+    });
+    let computer = match computer {
+        Ok(computer) => computer,
+        Err(error) => {
+            println!("The computer could not place its ships: {}", error);
+            return;
+        }
+    };
+
+    let player_one_board = player_setup(Player::PlayerOne, board_size, &fleet);
+
+    let mut game = Game::new(board_size, fleet);
+    if let Err(error) = game
+        .place_fleet(Player::PlayerOne, player_one_board)
+        .and_then(|_| game.place_fleet(Player::PlayerTwo, computer.computer_board.clone()))
+    {
+        panic!("the fleets could not be placed: {}", error);
+    }
+
+    play_singleplayer_game(difficulty, game, computer, record_path, None, &profile);
+}
+
+pub fn resume_singleplayer_game(snapshot: GameSnapshot, profile: &str) {
+    let Some(saved_computer) = snapshot.computer else {
+        panic!("resumed a single player game without a computer");
     };
-    let computer_board = computer.computer_board;
 
-    let mut defender_board = computer_board.clone();
+    let mut computer = Computer::restore(
+        difficulty_attack_strategy(
+            saved_computer.difficulty,
+            profile,
+            snapshot.player_two_board.get_size(),
+        ),
+        snapshot.player_two_board.clone(),
+        saved_computer.enemy_view,
+    );
+    if let Err(error) = computer.load_memory(&saved_computer.memory) {
+        println!("{}, the computer starts with a clear memory", error);
+    }
+
+    let game = match Game::resume(
+        snapshot.player_one_board,
+        snapshot.player_two_board,
+        Player::PlayerOne,
+    ) {
+        Ok(game) => game,
+        Err(error) => {
+            println!("The saved game can not be resumed: {}", error);
+            return;
+        }
+    };
+    GameSnapshot::remove_save();
+
+    let (record_path, game_record) = resume_game_record(
+        snapshot.record_path,
+        game.get_board(Player::PlayerOne),
+        game.get_board(Player::PlayerTwo),
+    );
+
+    play_singleplayer_game(
+        saved_computer.difficulty,
+        game,
+        computer,
+        record_path,
+        game_record,
+        profile,
+    );
+}
+
+fn difficulty_attack_strategy(
+    difficulty: Difficulty,
+    profile: &str,
+    board_size: BoardSize,
+) -> Box<dyn AttackStrategy> {
+    match difficulty {
+        Difficulty::Easy => Box::new(RandomAttackStrategy),
+        Difficulty::Medium => Box::new(HuntAndTargetAttackStrategy::new()),
+        // the density strategy keeps no memory of its own, so saves from when hard used the
+        // probability strategy still resume
+        Difficulty::Hard => Box::new(ProbabilityDensityAttackStrategy),
+        Difficulty::Expert(settings) => Box::new(MonteCarloAttackStrategy::new(settings)),
+        Difficulty::Adaptive => {
+            let heatmap = PlacementHeatmap::load(profile, board_size).unwrap_or_else(|error| {
+                println!("{}, the computer starts without knowing your habits", error);
+                PlacementHeatmap::new(profile, board_size)
+            });
+            Box::new(AdaptiveAttackStrategy::new(heatmap))
+        }
+    }
+}
+
+fn play_singleplayer_game(
+    difficulty: Difficulty,
+    mut game: Game,
+    mut computer: Computer,
+    record_path: Option<String>,
+    saved_record: Option<GameRecord>,
+    profile: &str,
+) {
+    let mut game_record = saved_record.unwrap_or_else(|| {
+        GameRecord::new(
+            &Player::PlayerOne.get_player_name(),
+            &format!("Computer ({})", difficulty.get_difficulty_name()),
+            game.get_board(Player::PlayerOne),
+            game.get_board(Player::PlayerTwo),
+        )
+    });
+
+    // the 4 is for the lines of numbers at the top of boards, and board labels
+    let board_lines = game.get_board_size().get_height() as usize * 2 + 4;
 
     loop {
-        // the 4 is for the lines of numbers at the top of boards, and board labels
-        let mut refresh_amount = defender_board.board.len() + player_one_board.board.len() + 4;
-
-        let player_turn_result = player_turn(
-            defender_board,
-            &"Computer".to_string(),
-            player_one_board,
-            refresh_amount,
-        );
+        let mut refresh_amount = board_lines;
+
+        let (attack_position, shot_outcome) =
+            match player_turn(&mut game, &"Computer".to_string(), refresh_amount) {
+                PlayerTurnResult::Fired {
+                    attack_position,
+                    shot_outcome,
+                } => (attack_position, shot_outcome),
+                PlayerTurnResult::SaveAndQuit => {
+                    write_game_records(record_path.as_deref(), &[game_record]);
+
+                    let snapshot = GameSnapshot {
+                        current_player: Player::PlayerOne,
+                        player_one_board: game.get_board(Player::PlayerOne).clone(),
+                        player_two_board: game.get_board(Player::PlayerTwo).clone(),
+                        computer: Some(SavedComputer {
+                            difficulty,
+                            enemy_view: computer.enemy_view.clone(),
+                            memory: computer.save_memory(),
+                        }),
+                        record_path: record_path.clone(),
+                    };
+
+                    match snapshot.save() {
+                        Ok(()) => println!("Game saved, pick \"Resume saved game\" to continue it"),
+                        Err(error) => println!("{}", error),
+                    }
+                    break;
+                }
+            };
 
-        defender_board = player_turn_result.defender_board;
+        game_record.add_shot(Player::PlayerOne, attack_position, shot_outcome.clone());
 
-        if player_turn_result.sunk_a_ship {
-            println!("");
-            println!(
-                "You sunk Computers {}!",
-                player_turn_result.tile_at_attack.get_tile_type_name()
-            );
-            println!("");
+        if let ShotOutcome::Sunk(sunk_ship) | ShotOutcome::Won(sunk_ship) = &shot_outcome {
+            println!();
+            println!("You sunk Computers {}!", sunk_ship.name);
+            println!();
 
             refresh_amount += 3;
         }
 
-        if player_turn_result.won_the_game {
+        if game.winner() == Some(Player::PlayerOne) {
             println!("You won the game!");
+            write_game_records(record_path.as_deref(), &[game_record]);
+            record_placement(profile, game.get_board(Player::PlayerOne));
             break;
         }
 
         refresh_display(refresh_amount as u16);
 
-        let computer_attack_position = computer
-            .attack_strategy
-            .calculate_best_attack(&player_one_board);
-
-        let feedback = process_attack(player_one_board, computer_attack_position);
+        let computer_attack_position = computer.calculate_attack(game.get_board(Player::PlayerOne));
+
+        let shot_outcome = match game.fire(Player::PlayerTwo, computer_attack_position) {
+            Ok(shot_outcome) => shot_outcome,
+            Err(error) => panic!("the computer made an invalid attack: {}", error),
+        };
+        computer.record_attack_result(computer_attack_position, &shot_outcome);
+        game_record.add_shot(
+            Player::PlayerTwo,
+            computer_attack_position,
+            shot_outcome.clone(),
+        );
 
-        if feedback.valid_attack {
-            match feedback.tile_at_attack {
-                Tile::Unknown => {
-                    player_one_board.place_marker_on_board(computer_attack_position, Tile::Miss)
-                }
-                Tile::Ship(_) => {
-                    player_one_board.place_marker_on_board(computer_attack_position, Tile::Hit)
-                }
-                _ => player_one_board.place_marker_on_board(computer_attack_position, Tile::Miss),
+        if let ShotOutcome::Sunk(sunk_ship) | ShotOutcome::Won(sunk_ship) = &shot_outcome {
+            println!();
+            let mut confirm = false;
+            while !confirm {
+                confirm = Confirm::new()
+                    .set_message(format!(
+                        "The computer sunk your {}! Press enter to continue",
+                        sunk_ship.name
+                    ))
+                    .ask();
             }
+            println!();
 
-            if feedback.sunk_a_ship {
-                let sunk_ship_type = match feedback.tile_at_attack {
-                    Tile::Ship(ship) => ship.get_ship_type_name(),
-                    _ => panic!("sunk ship trigger on a tile that is not a ship"),
-                };
-
-                println!("");
-                let mut confirm = false;
-                while !confirm {
-                    confirm = Confirm::new()
-                        .set_message(format!(
-                            "The computer sunk your {}! Press enter to continue",
-                            sunk_ship_type
-                        ))
-                        .ask();
-                }
-                println!("");
-
-                refresh_display(2)
-            }
+            refresh_display(2)
+        }
 
-            if feedback.won_the_game {
-                println!("The computer won the game!");
-                break;
-            }
+        if game.winner() == Some(Player::PlayerTwo) {
+            println!("The computer won the game!");
+            write_game_records(record_path.as_deref(), &[game_record]);
+            record_placement(profile, game.get_board(Player::PlayerOne));
+            break;
         }
     }
 }
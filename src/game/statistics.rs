@@ -0,0 +1,237 @@
+// the z score of a 95% confidence interval
+pub const CONFIDENCE_95: f64 = 1.96;
+
+// how many characters the longest bar of a histogram takes up
+const HISTOGRAM_BAR_LENGTH: usize = 40;
+
+/// The usual summary of a list of numbers, every value is 0 for an empty list.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub standard_deviation: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Summary {
+    pub fn new(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted = values.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let count = sorted.len();
+        let mean = sorted.iter().sum::<f64>() / count as f64;
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2.0
+        } else {
+            sorted[count / 2]
+        };
+        // the sample standard deviation, a single value does not vary
+        let standard_deviation = if count > 1 {
+            let squared_differences: f64 = sorted.iter().map(|value| (value - mean).powi(2)).sum();
+            (squared_differences / (count - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+
+        Self {
+            count,
+            mean,
+            median,
+            standard_deviation,
+            min: sorted[0],
+            max: sorted[count - 1],
+        }
+    }
+
+    pub fn get_summary_line(&self) -> String {
+        format!(
+            "mean {:.2}, median {}, std dev {:.2}, min {}, max {}",
+            self.mean,
+            format_value(self.median),
+            self.standard_deviation,
+            format_value(self.min),
+            format_value(self.max)
+        )
+    }
+}
+
+// whole numbers, such as shot counts, are shown without decimals
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+/// The value below which the given percentage of the values fall, interpolated between the two
+/// closest values, 0 for an empty list.
+pub fn percentile(values: &[f64], percentage: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let rank = (percentage / 100.0).clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// The Wilson score interval of a proportion, which stays within 0 and 1 and holds up for small
+/// numbers of trials or proportions close to 0 or 1.
+pub fn wilson_interval(successes: usize, trials: usize, z: f64) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 0.0);
+    }
+
+    let trials = trials as f64;
+    let proportion = successes as f64 / trials;
+    let z_squared = z * z;
+
+    let center = proportion + z_squared / (2.0 * trials);
+    let margin =
+        z * (proportion * (1.0 - proportion) / trials + z_squared / (4.0 * trials * trials)).sqrt();
+    let denominator = 1.0 + z_squared / trials;
+
+    (
+        ((center - margin) / denominator).max(0.0),
+        ((center + margin) / denominator).min(1.0),
+    )
+}
+
+/// Counts the values into buckets of the given width, starting at `start`, and returns the start of
+/// every bucket with its count.
+pub fn histogram(values: &[f64], start: f64, bucket_width: f64) -> Vec<(f64, usize)> {
+    if values.is_empty() || bucket_width <= 0.0 {
+        return Vec::new();
+    }
+
+    let max = values.iter().cloned().fold(f64::MIN, f64::max);
+    let bucket_count = ((max - start) / bucket_width).floor() as usize + 1;
+    let mut buckets = vec![0; bucket_count];
+
+    for value in values {
+        let bucket = ((value - start) / bucket_width).floor().max(0.0) as usize;
+        buckets[bucket.min(bucket_count - 1)] += 1;
+    }
+
+    buckets
+        .into_iter()
+        .enumerate()
+        .map(|(bucket, count)| (start + bucket as f64 * bucket_width, count))
+        .collect()
+}
+
+// a bucket width that splits the range of the values into about the given number of buckets
+pub fn get_bucket_width(summary: &Summary, buckets: usize) -> f64 {
+    ((summary.max - summary.min) / buckets as f64)
+        .ceil()
+        .max(1.0)
+}
+
+// the label gets the first and last value of the bucket
+pub fn print_histogram(
+    buckets: &[(f64, usize)],
+    bucket_width: f64,
+    label: impl Fn(f64, f64) -> String,
+) {
+    let largest_count = buckets.iter().map(|(_, count)| *count).max().unwrap_or(0);
+
+    for (bucket_start, count) in buckets {
+        let bar_length = if largest_count == 0 {
+            0
+        } else {
+            (count * HISTOGRAM_BAR_LENGTH).div_ceil(largest_count)
+        };
+
+        println!(
+            "{:>15} | {:<width$} {}",
+            label(*bucket_start, bucket_start + bucket_width),
+            "#".repeat(bar_length),
+            count,
+            width = HISTOGRAM_BAR_LENGTH
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-4,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn summary_of_values() {
+        let summary = Summary::new(&[4.0, 1.0, 3.0, 2.0]);
+
+        assert_eq!(summary.count, 4);
+        assert_close(summary.mean, 2.5);
+        assert_close(summary.median, 2.5);
+        assert_close(summary.standard_deviation, 1.2910);
+        assert_eq!((summary.min, summary.max), (1.0, 4.0));
+
+        assert_close(Summary::new(&[3.0, 1.0, 2.0]).median, 2.0);
+        assert_eq!(Summary::new(&[7.0]).standard_deviation, 0.0);
+        assert_eq!(Summary::new(&[]), Summary::default());
+    }
+
+    #[test]
+    fn percentile_interpolates_between_values() {
+        let values = [10.0, 40.0, 20.0, 30.0];
+
+        assert_close(percentile(&values, 0.0), 10.0);
+        assert_close(percentile(&values, 50.0), 25.0);
+        assert_close(percentile(&values, 90.0), 37.0);
+        assert_close(percentile(&values, 100.0), 40.0);
+        assert_close(percentile(&values, 150.0), 40.0);
+        assert_eq!(percentile(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn wilson_interval_stays_within_bounds() {
+        let (lower, upper) = wilson_interval(5, 10, CONFIDENCE_95);
+        assert_close(lower, 0.2366);
+        assert_close(upper, 0.7634);
+
+        let (lower, upper) = wilson_interval(0, 10, CONFIDENCE_95);
+        assert_eq!(lower, 0.0);
+        assert_close(upper, 0.2775);
+
+        let (lower, upper) = wilson_interval(10, 10, CONFIDENCE_95);
+        assert_close(lower, 0.7225);
+        assert_eq!(upper, 1.0);
+
+        assert_eq!(wilson_interval(0, 0, CONFIDENCE_95), (0.0, 0.0));
+    }
+
+    #[test]
+    fn histogram_counts_values_into_buckets() {
+        let values = [1.0, 2.0, 4.9, 5.0, 12.0];
+
+        assert_eq!(
+            histogram(&values, 0.0, 5.0),
+            vec![(0.0, 3), (5.0, 1), (10.0, 1)]
+        );
+        // values below the start land in the first bucket
+        assert_eq!(histogram(&[-3.0, 1.0], 0.0, 2.0), vec![(0.0, 2)]);
+        assert!(histogram(&[], 0.0, 5.0).is_empty());
+        assert!(histogram(&values, 0.0, 0.0).is_empty());
+    }
+}
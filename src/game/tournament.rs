@@ -0,0 +1,334 @@
+use crate::display::create_progress_bar;
+use crate::game::simulation::{run_simulation, SimulationResults};
+use crate::{ComputerAttackStrategy, SimulationConfig, TournamentConfig};
+
+// the rating every strategy would have if they were all as strong as each other
+const AVERAGE_RATING: f64 = 1500.0;
+// a strategy rated this much higher is expected to win ten games for every one it loses, as in elo
+const RATING_SCALE: f64 = 400.0;
+// half a win is added to both sides of every match, so that a strategy that wins or loses every
+// game still gets a finite rating
+const RATING_PRIOR_WINS: f64 = 0.5;
+const RATING_ITERATIONS: usize = 1000;
+const RATING_TOLERANCE: f64 = 1e-9;
+
+/// The results of every match of a tournament, see [`run_tournament`].
+pub struct TournamentResults {
+    pub strategies: Vec<ComputerAttackStrategy>,
+    // one match for every pair of strategies, mirror matches included, the strategy that comes
+    // first in `strategies` is always computer one
+    pub matches: Vec<SimulationResults>,
+    pub seed: u64,
+}
+
+impl TournamentResults {
+    pub fn get_match(
+        &self,
+        strategy: ComputerAttackStrategy,
+        opponent: ComputerAttackStrategy,
+    ) -> Option<&SimulationResults> {
+        self.matches.iter().find(|results| {
+            (results.computer_one_attack_strategy == strategy
+                && results.computer_two_attack_strategy == opponent)
+                || (results.computer_one_attack_strategy == opponent
+                    && results.computer_two_attack_strategy == strategy)
+        })
+    }
+
+    /// How many games the strategy won and played against the opponent, in a mirror match these
+    /// are the wins of computer one.
+    pub fn get_record(
+        &self,
+        strategy: ComputerAttackStrategy,
+        opponent: ComputerAttackStrategy,
+    ) -> (i32, i32) {
+        match self.get_match(strategy, opponent) {
+            Some(results) if results.computer_one_attack_strategy == strategy => {
+                (results.computer_one_wins, results.games_played)
+            }
+            Some(results) => (results.computer_two_wins, results.games_played),
+            None => (0, 0),
+        }
+    }
+
+    /// Bradley-Terry ratings from every match between two different strategies, on the same scale
+    /// as elo ratings, from the highest rated strategy to the lowest.
+    pub fn get_ratings(&self) -> Vec<(ComputerAttackStrategy, f64)> {
+        let strategy_count = self.strategies.len();
+        let mut wins = vec![vec![0.0; strategy_count]; strategy_count];
+
+        for (i, strategy) in self.strategies.iter().enumerate() {
+            for (j, opponent) in self.strategies.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+
+                let (strategy_wins, games_played) = self.get_record(*strategy, *opponent);
+                if games_played > 0 {
+                    wins[i][j] = strategy_wins as f64 + RATING_PRIOR_WINS;
+                }
+            }
+        }
+
+        let strengths = fit_bradley_terry(&wins);
+
+        let mut ratings: Vec<(ComputerAttackStrategy, f64)> = self
+            .strategies
+            .iter()
+            .zip(strengths)
+            .map(|(strategy, strength)| {
+                (*strategy, AVERAGE_RATING + RATING_SCALE * strength.log10())
+            })
+            .collect();
+        ratings.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        ratings
+    }
+
+    pub fn print_results(&self) {
+        println!();
+        println!("Tournament Results");
+        println!("------------------");
+        println!();
+
+        let games_per_match = self
+            .matches
+            .first()
+            .map_or(0, |results| results.games_played);
+        println!("Games per Match: {}", games_per_match);
+        println!("Seed: {}", self.seed);
+        println!();
+
+        self.print_cross_table();
+        self.print_ranking();
+    }
+
+    fn print_cross_table(&self) {
+        let name_width = self
+            .strategies
+            .iter()
+            .map(|strategy| strategy.get_attack_strategy_name().len())
+            .max()
+            .unwrap_or(0);
+
+        println!("Win Rate of Each Row Against Each Column");
+        println!("----------------------------------------");
+        println!();
+
+        let header: Vec<String> = self
+            .strategies
+            .iter()
+            .map(|strategy| {
+                format!(
+                    "{:>width$}",
+                    strategy.get_attack_strategy_name(),
+                    width = name_width
+                )
+            })
+            .collect();
+        println!("{:width$} | {}", "", header.join(" | "), width = name_width);
+
+        for strategy in &self.strategies {
+            let cells: Vec<String> = self
+                .strategies
+                .iter()
+                .map(|opponent| {
+                    let (wins, games_played) = self.get_record(*strategy, *opponent);
+                    let win_rate = if games_played == 0 {
+                        "-".to_string()
+                    } else {
+                        format!("{:.1}%", wins as f64 / games_played as f64 * 100.0)
+                    };
+
+                    format!("{:>width$}", win_rate, width = name_width)
+                })
+                .collect();
+
+            println!(
+                "{:width$} | {}",
+                strategy.get_attack_strategy_name(),
+                cells.join(" | "),
+                width = name_width
+            );
+        }
+
+        println!();
+        println!("Mirror matches show the win rate of computer one.");
+        println!();
+    }
+
+    fn print_ranking(&self) {
+        println!("Ranking");
+        println!("-------");
+        println!();
+
+        for (place, (strategy, rating)) in self.get_ratings().iter().enumerate() {
+            let (wins, games_played) = self
+                .strategies
+                .iter()
+                .filter(|opponent| *opponent != strategy)
+                .map(|opponent| self.get_record(*strategy, *opponent))
+                .fold((0, 0), |(total_wins, total_games), (wins, games_played)| {
+                    (total_wins + wins, total_games + games_played)
+                });
+
+            println!(
+                "{}. {}: {:.0} (won {} of {} games against other strategies)",
+                place + 1,
+                strategy.get_attack_strategy_name(),
+                rating,
+                wins,
+                games_played
+            );
+        }
+        println!();
+    }
+}
+
+// fits the strength of every strategy with the minorization-maximization algorithm, wins[i][j] is
+// how many games i won against j, the strengths are scaled so their geometric mean is 1
+fn fit_bradley_terry(wins: &[Vec<f64>]) -> Vec<f64> {
+    let strategy_count = wins.len();
+    let mut strengths = vec![1.0; strategy_count];
+
+    for _ in 0..RATING_ITERATIONS {
+        let mut next_strengths: Vec<f64> = (0..strategy_count)
+            .map(|i| {
+                let total_wins: f64 = wins[i].iter().sum();
+                let denominator: f64 = (0..strategy_count)
+                    .filter(|j| *j != i)
+                    .map(|j| (wins[i][j] + wins[j][i]) / (strengths[i] + strengths[j]))
+                    .sum();
+
+                if denominator == 0.0 {
+                    strengths[i]
+                } else {
+                    total_wins / denominator
+                }
+            })
+            .collect();
+
+        let geometric_mean = (next_strengths
+            .iter()
+            .map(|strength| strength.ln())
+            .sum::<f64>()
+            / strategy_count as f64)
+            .exp();
+        for strength in next_strengths.iter_mut() {
+            *strength /= geometric_mean;
+        }
+
+        let change = strengths
+            .iter()
+            .zip(&next_strengths)
+            .map(|(strength, next_strength)| (strength - next_strength).abs())
+            .fold(0.0, f64::max);
+        strengths = next_strengths;
+
+        if change < RATING_TOLERANCE {
+            break;
+        }
+    }
+
+    strengths
+}
+
+pub fn tournament(tournament_config: TournamentConfig) {
+    println!("Playing Tournament...");
+
+    let tournament_results = run_tournament(&tournament_config, create_progress_bar);
+
+    tournament_results.print_results();
+}
+
+/// Plays a match between every pair of strategies, mirror matches included, each one a
+/// simulation of `games_per_match` games.
+///
+/// Every match is played with the tournament's seed, so game `n` of each match starts from the
+/// same random numbers. `on_game_played` is called with the games played so far over the whole
+/// tournament and the number of games to play.
+pub fn run_tournament(
+    tournament_config: &TournamentConfig,
+    mut on_game_played: impl FnMut(i32, i32),
+) -> TournamentResults {
+    let seed = tournament_config.seed.unwrap_or_else(rand::random);
+    let strategies = tournament_config.strategies.clone();
+
+    let mut pairings = Vec::new();
+    for (i, strategy) in strategies.iter().enumerate() {
+        for opponent in &strategies[i..] {
+            pairings.push((*strategy, *opponent));
+        }
+    }
+
+    let games_per_match = tournament_config.games_per_match.max(0);
+    let games_to_play = games_per_match * pairings.len() as i32;
+    let mut matches = Vec::new();
+
+    for (match_index, (strategy, opponent)) in pairings.into_iter().enumerate() {
+        let mut simulation_config = SimulationConfig::new(
+            strategy,
+            opponent,
+            games_per_match,
+            tournament_config.board_size,
+            tournament_config.fleet.clone(),
+        );
+        simulation_config.seed = Some(seed);
+
+        let games_played_before = games_per_match * match_index as i32;
+        let (simulation_results, _) = run_simulation(&simulation_config, |games_played, _| {
+            on_game_played(games_played_before + games_played, games_to_play)
+        });
+
+        matches.push(simulation_results);
+    }
+
+    TournamentResults {
+        strategies,
+        matches,
+        seed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < 1e-6,
+                "expected {:?}, got {:?}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn bradley_terry_fits_the_win_ratio_of_two_strategies() {
+        let strengths = fit_bradley_terry(&[vec![0.0, 3.0], vec![1.0, 0.0]]);
+
+        assert_close(&strengths, &[3.0_f64.sqrt(), 1.0 / 3.0_f64.sqrt()]);
+    }
+
+    #[test]
+    fn bradley_terry_recovers_the_strengths_the_wins_came_from() {
+        // 15 games per match between strategies of strength 4, 2 and 1, each won as often as
+        // the model expects
+        let wins = vec![
+            vec![0.0, 10.0, 12.0],
+            vec![5.0, 0.0, 10.0],
+            vec![3.0, 5.0, 0.0],
+        ];
+
+        assert_close(&fit_bradley_terry(&wins), &[2.0, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn bradley_terry_rates_even_strategies_the_same() {
+        let strengths = fit_bradley_terry(&[vec![0.0, 5.0], vec![5.0, 0.0]]);
+
+        assert_close(&strengths, &[1.0, 1.0]);
+    }
+}
@@ -0,0 +1,729 @@
+//! Battleship for the terminal, and the engine it is built on.
+//!
+//! The rules live in [`Game`], which knows nothing about the terminal, so it can be driven by
+//! anything that can pick a [`Position`] to fire at. The computer players are [`Computer`]s with
+//! an [`AttackStrategy`], and [`run_simulation`] pits two of them against each other.
+//!
+//! ```
+//! use terminal_battleship::game::computer::computer_setup::computer_setup;
+//! use terminal_battleship::utils::create_rng;
+//! use terminal_battleship::{BoardSize, FleetDefinition, Game, Player, Position, ShotOutcome};
+//!
+//! let board_size = BoardSize::default();
+//! let fleet = FleetDefinition::classic();
+//! let mut rng = create_rng(Some(7));
+//!
+//! let mut game = Game::new(board_size, fleet.clone());
+//! game.place_fleet(Player::PlayerOne, computer_setup(board_size, &fleet, &mut rng)?)?;
+//! game.place_fleet(Player::PlayerTwo, computer_setup(board_size, &fleet, &mut rng)?)?;
+//!
+//! let outcome = game.fire(Player::PlayerOne, Position::new(0, 0)).unwrap();
+//! assert!(matches!(outcome, ShotOutcome::Miss | ShotOutcome::Hit | ShotOutcome::Sunk(_)));
+//! assert_eq!(game.current_turn(), Player::PlayerTwo);
+//! # Ok::<(), terminal_battleship::GameError>(())
+//! ```
+
+pub mod cli;
+pub mod display;
+pub mod game;
+pub mod setup;
+pub mod utils;
+
+pub use game::benchmark::{run_benchmark, BenchmarkResults};
+pub use game::computer::{AttackStrategy, Computer, OmniscientAttackStrategy, PlacementStrategy};
+pub use game::engine::{Game, GameError, GamePhase};
+pub use game::export::{export_simulation_results, ExportFormat};
+pub use game::fleet::{Fleet, FleetDefinition, ShipClass};
+pub use game::observed_board::{ObservedBoard, ObservedTile};
+pub use game::placement_benchmark::{run_placement_benchmark, PlacementBenchmarkResults};
+pub use game::simulation::{run_simulation, SimulationResult, SimulationResults};
+pub use game::tournament::{run_tournament, TournamentResults};
+pub use game::{place_ship_on_board, process_attack, AttackFeedback, ShotOutcome};
+
+use game::computer::computers::monte_carlo::MonteCarloSettings;
+use game::computer::placements::anti_density::AntiDensityPlacement;
+use game::computer::placements::edge_heavy::EdgeHeavyPlacement;
+use game::computer::placements::no_touching::NoTouchingPlacement;
+use game::computer::placements::spread_out::SpreadOutPlacement;
+use game::computer::placements::uniform::UniformPlacement;
+use game::heatmap::DEFAULT_PROFILE;
+use game::save::GameSnapshot;
+
+/// The width and height of the classic board.
+pub const DEFAULT_BOARD_SIZE: i8 = 10;
+/// The smallest width or height a board can have.
+pub const MIN_BOARD_SIZE: i8 = 6;
+/// The largest width or height a board can have, rows are labeled with letters so boards can
+/// not be taller or wider than the alphabet.
+pub const MAX_BOARD_SIZE: i8 = 26;
+/// Prints the probability grid of the probability attack strategy while it is thinking.
+pub const DEBUG: bool = false;
+
+/// A single square of a board.
+///
+/// `Targeted` and `AlreadyAttacked` only exist while a player is aiming, they are never stored
+/// on a board that is being played on.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Tile {
+    Targeted,
+    AlreadyAttacked,
+    Hit,
+    Miss,
+    Unknown,
+    Ship(Ship),
+}
+
+impl Tile {
+    pub fn get_tile_display(&self) -> String {
+        match self {
+            Tile::Targeted => " ⊕ ".to_string(),
+            Tile::AlreadyAttacked => " ⊗ ".to_string(),
+            Tile::Hit => " 🅇 ".to_string(),
+            Tile::Miss => " ⓪ ".to_string(),
+            Tile::Unknown => " • ".to_string(),
+            Tile::Ship(ship) => ship.get_ship_display(),
+        }
+    }
+
+    pub fn get_tile_type_name(&self) -> String {
+        match self {
+            Tile::Targeted => "Targeted".to_string(),
+            Tile::AlreadyAttacked => "AlreadyAttacked".to_string(),
+            Tile::Hit => "Hit".to_string(),
+            Tile::Miss => "Miss".to_string(),
+            Tile::Unknown => "Unknown".to_string(),
+            Tile::Ship(_) => "Ship".to_string(),
+        }
+    }
+}
+
+/// Every placed ship has its own id, which is also its index in the fleet definition of the board.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct ShipId(pub u8);
+
+/// A ship that is placed, or about to be placed, on a board.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Ship {
+    id: ShipId,
+    length: u8,
+    orientation: ShipOrientation,
+}
+
+impl Ship {
+    pub fn new(id: ShipId, length: u8, orientation: ShipOrientation) -> Self {
+        Self {
+            id,
+            length,
+            orientation,
+        }
+    }
+
+    pub fn get_id(&self) -> ShipId {
+        self.id
+    }
+
+    pub fn get_ship_length(&self) -> u8 {
+        self.length
+    }
+
+    pub fn get_orientation(&self) -> ShipOrientation {
+        self.orientation
+    }
+
+    pub fn rotate(&mut self) {
+        self.orientation = match self.orientation {
+            ShipOrientation::Horizontal => ShipOrientation::Vertical,
+            ShipOrientation::Vertical => ShipOrientation::Horizontal,
+        };
+    }
+
+    pub fn get_ship_display(&self) -> String {
+        match (self.length, self.orientation) {
+            (5.., _) => " ▧ ".to_string(),
+            (4, _) => " # ".to_string(),
+            (3, ShipOrientation::Horizontal) => " ▭ ".to_string(),
+            (3, ShipOrientation::Vertical) => " ▯ ".to_string(),
+            (2, _) => " △ ".to_string(),
+            _ => " ◇ ".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ShipOrientation {
+    Horizontal,
+    Vertical,
+}
+
+/// The width and height of a board, in tiles.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BoardSize {
+    pub width: i8,
+    pub height: i8,
+}
+
+impl Default for BoardSize {
+    fn default() -> Self {
+        Self::new(DEFAULT_BOARD_SIZE, DEFAULT_BOARD_SIZE)
+    }
+}
+
+impl BoardSize {
+    pub fn new(width: i8, height: i8) -> Self {
+        Self { width, height }
+    }
+
+    pub fn get_width(&self) -> i8 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> i8 {
+        self.height
+    }
+
+    /// Whether both sides are between [`MIN_BOARD_SIZE`] and [`MAX_BOARD_SIZE`].
+    pub fn is_valid(&self) -> bool {
+        (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&self.width)
+            && (MIN_BOARD_SIZE..=MAX_BOARD_SIZE).contains(&self.height)
+    }
+
+    pub fn get_center(&self) -> Position {
+        Position::new((self.height - 1) / 2, (self.width - 1) / 2)
+    }
+}
+
+/// The tiles of a board, indexed by row and then column.
+pub type Board = Vec<Vec<Tile>>;
+
+/// The board of one player, with the ships of their fleet on it and every shot fired at it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GameBoard {
+    pub board: Board,
+    size: BoardSize,
+    fleet: Fleet,
+}
+
+impl Default for GameBoard {
+    fn default() -> Self {
+        Self::new(BoardSize::default(), FleetDefinition::default())
+    }
+}
+
+impl GameBoard {
+    pub fn new(size: BoardSize, fleet: FleetDefinition) -> Self {
+        Self {
+            board: vec![vec![Tile::Unknown; size.get_width() as usize]; size.get_height() as usize],
+            size,
+            fleet: Fleet::new(fleet),
+        }
+    }
+
+    pub fn get_size(&self) -> BoardSize {
+        self.size
+    }
+
+    pub fn get_fleet(&self) -> &Fleet {
+        &self.fleet
+    }
+
+    pub fn get_fleet_definition(&self) -> &FleetDefinition {
+        self.fleet.get_definition()
+    }
+
+    /// Places the ship with its top left end at the position, the board is left untouched when
+    /// the ship can not go there.
+    pub fn place_ship(&mut self, ship: Ship, position: Position) -> Result<(), GameError> {
+        match self
+            .get_fleet_definition()
+            .ships
+            .get(ship.get_id().0 as usize)
+        {
+            Some(ship_class) if ship_class.length == ship.get_ship_length() => {}
+            _ => return Err(GameError::ShipNotInFleet),
+        }
+
+        if self.fleet.is_placed(ship.get_id()) {
+            return Err(GameError::ShipAlreadyPlaced);
+        }
+
+        let length = ship.get_ship_length() as i8 - 1;
+        let end_position = match ship.get_orientation() {
+            ShipOrientation::Horizontal => {
+                Position::new(position.get_y(), position.get_x() + length)
+            }
+            ShipOrientation::Vertical => Position::new(position.get_y() + length, position.get_x()),
+        };
+
+        if !position.is_on_board(self.size) || !end_position.is_on_board(self.size) {
+            return Err(GameError::ShipOffBoard);
+        }
+
+        let (valid, board) = place_ship_on_board(
+            self.board.clone(),
+            &ship,
+            position.get_y() as usize,
+            position.get_x() as usize,
+            false,
+        );
+
+        if !valid {
+            return Err(GameError::ShipsOverlap);
+        }
+
+        self.board = board;
+        self.fleet.add_placed_ship(ship, position);
+
+        Ok(())
+    }
+
+    /// Whether a ship of the length still fits somewhere on the board, either way round.
+    pub fn has_room_for(&self, ship_length: u8) -> bool {
+        let ship_length = ship_length as usize;
+
+        self.board.iter().enumerate().any(|(row, tiles)| {
+            (0..tiles.len()).any(|col| {
+                let fits_horizontally = col + ship_length <= tiles.len()
+                    && tiles[col..col + ship_length]
+                        .iter()
+                        .all(|tile| *tile == Tile::Unknown);
+                let fits_vertically = row + ship_length <= self.board.len()
+                    && self.board[row..row + ship_length]
+                        .iter()
+                        .all(|tiles| tiles[col] == Tile::Unknown);
+
+                fits_horizontally || fits_vertically
+            })
+        })
+    }
+
+    /// Returns true when the hit sunk the ship.
+    pub fn register_hit(&mut self, id: ShipId) -> bool {
+        self.fleet.register_hit(id)
+    }
+
+    pub fn place_marker_on_board(&mut self, position: Position, tile: Tile) {
+        if position.is_on_board(self.size) {
+            self.board[position.get_y() as usize][position.get_x() as usize] = tile;
+        }
+    }
+
+    pub fn get_tile_at_position(&self, position: Position) -> Tile {
+        self.board[position.get_y() as usize][position.get_x() as usize]
+    }
+}
+
+/// A tile on a board, `y` is the row and `x` the column, both starting at 0.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub y: i8,
+    pub x: i8,
+}
+
+impl Position {
+    pub fn new(y: i8, x: i8) -> Position {
+        Position { y, x }
+    }
+
+    pub fn set_y(&mut self, y: i8) {
+        self.y = y;
+    }
+
+    pub fn set_x(&mut self, x: i8) {
+        self.x = x;
+    }
+
+    pub fn get_y(&self) -> i8 {
+        self.y
+    }
+
+    pub fn get_x(&self) -> i8 {
+        self.x
+    }
+
+    pub fn is_on_board(&self, board_size: BoardSize) -> bool {
+        self.y >= 0
+            && self.y < board_size.get_height()
+            && self.x >= 0
+            && self.x < board_size.get_width()
+    }
+}
+
+/// One of the two sides of a game.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Player {
+    PlayerOne,
+    PlayerTwo,
+}
+
+impl Player {
+    pub fn get_player_name(&self) -> String {
+        match self {
+            Player::PlayerOne => "Player One".to_string(),
+            Player::PlayerTwo => "Player Two".to_string(),
+        }
+    }
+
+    pub fn get_other_player(&self) -> Player {
+        match self {
+            Player::PlayerOne => Player::PlayerTwo,
+            Player::PlayerTwo => Player::PlayerOne,
+        }
+    }
+}
+
+/// How well the computer plays in a single player game.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    // samples whole layouts of the player's fleet, the settings come from the command line
+    Expert(MonteCarloSettings),
+    // learns where the player tends to put their ships from their earlier games
+    Adaptive,
+}
+
+impl Difficulty {
+    pub fn get_difficulty_name(&self) -> String {
+        match self {
+            Difficulty::Easy => "Easy".to_string(),
+            Difficulty::Medium => "Medium".to_string(),
+            Difficulty::Hard => "Hard".to_string(),
+            Difficulty::Expert(_) => "Expert".to_string(),
+            Difficulty::Adaptive => "Adaptive".to_string(),
+        }
+    }
+
+    // only changes expert difficulty, every other difficulty has nothing to set
+    pub fn with_monte_carlo_settings(self, settings: MonteCarloSettings) -> Self {
+        match self {
+            Difficulty::Expert(_) => Difficulty::Expert(settings),
+            difficulty => difficulty,
+        }
+    }
+
+    /// How the computer hides its ships. Easy places them anywhere, medium keeps them apart
+    /// because a hunt and target search gains nothing from ships that touch, and the harder
+    /// difficulties stay off the tiles a probability search fires at first.
+    pub fn get_placement_strategy(&self) -> ComputerPlacementStrategy {
+        match self {
+            Difficulty::Easy => ComputerPlacementStrategy::Uniform,
+            Difficulty::Medium => ComputerPlacementStrategy::NoTouching,
+            Difficulty::Hard => ComputerPlacementStrategy::AntiDensity,
+            Difficulty::Expert(_) => ComputerPlacementStrategy::AntiDensity,
+            Difficulty::Adaptive => ComputerPlacementStrategy::AntiDensity,
+        }
+    }
+}
+
+/// The kind of game picked in the terminal front-end.
+pub enum GameMode {
+    SinglePlayer,
+    MultiPlayer,
+    ComputerFight,
+    Tournament,
+    Benchmark,
+    PlacementBenchmark,
+    NetworkHost,
+    NetworkJoin,
+}
+
+/// Everything the terminal front-end needs to start the game that was picked.
+pub struct GameConfig {
+    pub game_mode: GameMode,
+    pub difficulty: Option<Difficulty>,
+    pub simulation_config: Option<SimulationConfig>,
+    pub tournament_config: Option<TournamentConfig>,
+    pub benchmark_config: Option<BenchmarkConfig>,
+    pub placement_benchmark_config: Option<PlacementBenchmarkConfig>,
+    pub board_size: BoardSize,
+    pub fleet: FleetDefinition,
+    pub saved_game: Option<GameSnapshot>,
+    pub record_path: Option<String>,
+    // the address to listen on when hosting, or to connect to when joining
+    pub network_address: String,
+    // seeds the computer's board and attacks, so the same moves replay the same game
+    pub seed: Option<u64>,
+    // whose ship placement habits the adaptive computer learns
+    pub profile: String,
+}
+
+impl GameConfig {
+    pub fn new(
+        game_mode: GameMode,
+        difficulty: Option<Difficulty>,
+        simulation_config: Option<SimulationConfig>,
+    ) -> Self {
+        Self {
+            game_mode,
+            difficulty,
+            simulation_config,
+            tournament_config: None,
+            benchmark_config: None,
+            placement_benchmark_config: None,
+            board_size: BoardSize::default(),
+            fleet: FleetDefinition::default(),
+            saved_game: None,
+            record_path: None,
+            network_address: String::new(),
+            seed: None,
+            profile: DEFAULT_PROFILE.to_string(),
+        }
+    }
+
+    pub fn set_game_mode(&mut self, game_mode: GameMode) {
+        self.game_mode = game_mode;
+    }
+
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = Some(difficulty);
+    }
+
+    pub fn set_simulation_config(&mut self, simulation_config: SimulationConfig) {
+        self.simulation_config = Some(simulation_config);
+    }
+
+    pub fn set_tournament_config(&mut self, tournament_config: TournamentConfig) {
+        self.tournament_config = Some(tournament_config);
+    }
+
+    pub fn set_benchmark_config(&mut self, benchmark_config: BenchmarkConfig) {
+        self.benchmark_config = Some(benchmark_config);
+    }
+
+    pub fn set_placement_benchmark_config(
+        &mut self,
+        placement_benchmark_config: PlacementBenchmarkConfig,
+    ) {
+        self.placement_benchmark_config = Some(placement_benchmark_config);
+    }
+
+    pub fn set_board_size(&mut self, board_size: BoardSize) {
+        self.board_size = board_size;
+    }
+
+    pub fn set_fleet(&mut self, fleet: FleetDefinition) {
+        self.fleet = fleet;
+    }
+
+    pub fn set_record_path(&mut self, record_path: Option<String>) {
+        self.record_path = record_path;
+    }
+
+    pub fn set_network_address(&mut self, network_address: String) {
+        self.network_address = network_address;
+    }
+
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    pub fn set_profile(&mut self, profile: String) {
+        self.profile = profile;
+    }
+
+    pub fn set_saved_game(&mut self, saved_game: GameSnapshot) {
+        self.saved_game = Some(saved_game);
+    }
+}
+
+/// The attack strategies a computer can be given in a simulation.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ComputerAttackStrategy {
+    Random,
+    HuntAndTarget,
+    Probability,
+    ProbabilityDensity,
+    MonteCarlo(MonteCarloSettings),
+    Hacker,
+}
+
+impl ComputerAttackStrategy {
+    /// Every strategy, in the order they are listed in the menus.
+    pub fn all() -> Vec<ComputerAttackStrategy> {
+        vec![
+            ComputerAttackStrategy::Random,
+            ComputerAttackStrategy::HuntAndTarget,
+            ComputerAttackStrategy::Probability,
+            ComputerAttackStrategy::ProbabilityDensity,
+            ComputerAttackStrategy::MonteCarlo(MonteCarloSettings::default()),
+            ComputerAttackStrategy::Hacker,
+        ]
+    }
+
+    // only changes monte carlo strategies, every other strategy has nothing to set
+    pub fn with_monte_carlo_settings(self, settings: MonteCarloSettings) -> Self {
+        match self {
+            ComputerAttackStrategy::MonteCarlo(_) => ComputerAttackStrategy::MonteCarlo(settings),
+            attack_strategy => attack_strategy,
+        }
+    }
+
+    pub fn get_attack_strategy_name(&self) -> String {
+        match self {
+            ComputerAttackStrategy::Random => "Random".to_string(),
+            ComputerAttackStrategy::HuntAndTarget => "Hunt and Target".to_string(),
+            ComputerAttackStrategy::Probability => "Probability Attack".to_string(),
+            ComputerAttackStrategy::ProbabilityDensity => "Probability Density".to_string(),
+            ComputerAttackStrategy::MonteCarlo(_) => "Monte Carlo".to_string(),
+            ComputerAttackStrategy::Hacker => "Hacker".to_string(),
+        }
+    }
+}
+
+/// The ways a computer can place its ships, see [`PlacementStrategy`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ComputerPlacementStrategy {
+    Uniform,
+    NoTouching,
+    EdgeHeavy,
+    SpreadOut,
+    AntiDensity,
+}
+
+impl ComputerPlacementStrategy {
+    /// Every strategy, in the order they are listed in the menus.
+    pub fn all() -> Vec<ComputerPlacementStrategy> {
+        vec![
+            ComputerPlacementStrategy::Uniform,
+            ComputerPlacementStrategy::NoTouching,
+            ComputerPlacementStrategy::EdgeHeavy,
+            ComputerPlacementStrategy::SpreadOut,
+            ComputerPlacementStrategy::AntiDensity,
+        ]
+    }
+
+    pub fn get_placement_strategy(&self) -> Box<dyn PlacementStrategy> {
+        match self {
+            ComputerPlacementStrategy::Uniform => Box::new(UniformPlacement),
+            ComputerPlacementStrategy::NoTouching => Box::new(NoTouchingPlacement),
+            ComputerPlacementStrategy::EdgeHeavy => Box::new(EdgeHeavyPlacement),
+            ComputerPlacementStrategy::SpreadOut => Box::new(SpreadOutPlacement),
+            ComputerPlacementStrategy::AntiDensity => Box::new(AntiDensityPlacement),
+        }
+    }
+
+    pub fn get_placement_strategy_name(&self) -> String {
+        match self {
+            ComputerPlacementStrategy::Uniform => "Uniform".to_string(),
+            ComputerPlacementStrategy::NoTouching => "No Touching".to_string(),
+            ComputerPlacementStrategy::EdgeHeavy => "Edge Heavy".to_string(),
+            ComputerPlacementStrategy::SpreadOut => "Spread Out".to_string(),
+            ComputerPlacementStrategy::AntiDensity => "Anti Density".to_string(),
+        }
+    }
+}
+
+/// The games a simulation plays, see [`run_simulation`].
+pub struct SimulationConfig {
+    pub attack_strategy_one: ComputerAttackStrategy,
+    pub attack_strategy_two: ComputerAttackStrategy,
+    pub games_to_play: i32,
+    pub board_size: BoardSize,
+    pub fleet: FleetDefinition,
+    pub record_path: Option<String>,
+    // a .csv or .json file the results of every game are exported to
+    pub output_path: Option<String>,
+    // a random seed is picked when none is given
+    pub seed: Option<u64>,
+}
+
+impl SimulationConfig {
+    pub fn new(
+        attack_strategy_one: ComputerAttackStrategy,
+        attack_strategy_two: ComputerAttackStrategy,
+        games_to_play: i32,
+        board_size: BoardSize,
+        fleet: FleetDefinition,
+    ) -> Self {
+        Self {
+            attack_strategy_one,
+            attack_strategy_two,
+            games_to_play,
+            board_size,
+            fleet,
+            record_path: None,
+            output_path: None,
+            seed: None,
+        }
+    }
+}
+
+/// The matches a tournament plays, see [`run_tournament`].
+pub struct TournamentConfig {
+    pub strategies: Vec<ComputerAttackStrategy>,
+    pub games_per_match: i32,
+    pub board_size: BoardSize,
+    pub fleet: FleetDefinition,
+    // a random seed is picked when none is given
+    pub seed: Option<u64>,
+}
+
+impl TournamentConfig {
+    // every strategy takes part
+    pub fn new(games_per_match: i32, board_size: BoardSize, fleet: FleetDefinition) -> Self {
+        Self {
+            strategies: ComputerAttackStrategy::all(),
+            games_per_match,
+            board_size,
+            fleet,
+            seed: None,
+        }
+    }
+}
+
+/// The boards a benchmark clears, see [`run_benchmark`].
+pub struct BenchmarkConfig {
+    pub attack_strategy: ComputerAttackStrategy,
+    pub games_to_play: i32,
+    pub board_size: BoardSize,
+    pub fleet: FleetDefinition,
+    // a random seed is picked when none is given
+    pub seed: Option<u64>,
+}
+
+impl BenchmarkConfig {
+    pub fn new(
+        attack_strategy: ComputerAttackStrategy,
+        games_to_play: i32,
+        board_size: BoardSize,
+        fleet: FleetDefinition,
+    ) -> Self {
+        Self {
+            attack_strategy,
+            games_to_play,
+            board_size,
+            fleet,
+            seed: None,
+        }
+    }
+}
+
+/// The boards a placement benchmark clears, see [`run_placement_benchmark`].
+pub struct PlacementBenchmarkConfig {
+    pub placement_strategies: Vec<ComputerPlacementStrategy>,
+    pub attack_strategies: Vec<ComputerAttackStrategy>,
+    pub games_per_pairing: i32,
+    pub board_size: BoardSize,
+    pub fleet: FleetDefinition,
+    // a random seed is picked when none is given
+    pub seed: Option<u64>,
+}
+
+impl PlacementBenchmarkConfig {
+    // every placement strategy is cleared by every attack strategy, except the hacker, which
+    // knows where the ships are and so clears every placement in the same number of shots
+    pub fn new(games_per_pairing: i32, board_size: BoardSize, fleet: FleetDefinition) -> Self {
+        Self {
+            placement_strategies: ComputerPlacementStrategy::all(),
+            attack_strategies: ComputerAttackStrategy::all()
+                .into_iter()
+                .filter(|strategy| *strategy != ComputerAttackStrategy::Hacker)
+                .collect(),
+            games_per_pairing,
+            board_size,
+            fleet,
+            seed: None,
+        }
+    }
+}
@@ -1,5 +1,14 @@
-use crate::display::inputs::{NumberInput, OptionSelect};
-use crate::{ComputerAttackStrategy, Difficulty, GameConfig, GameMode, SimulationConfig};
+use crate::cli::CliOptions;
+use crate::display::inputs::{NumberInput, OptionSelect, TextInput};
+use crate::game::computer::computer_setup::check_fleet_fits;
+use crate::game::computer::computers::monte_carlo::MonteCarloSettings;
+use crate::game::fleet::FleetDefinition;
+use crate::game::network::{get_join_address, DEFAULT_PORT};
+use crate::game::save::GameSnapshot;
+use crate::{
+    BenchmarkConfig, BoardSize, ComputerAttackStrategy, Difficulty, GameConfig, GameMode,
+    PlacementBenchmarkConfig, SimulationConfig, TournamentConfig, MAX_BOARD_SIZE, MIN_BOARD_SIZE,
+};
 
 pub fn display_setup() {
     println!("==============================");
@@ -7,104 +16,351 @@ pub fn display_setup() {
     println!("      Created by cqb13        ");
     println!("      GitHub: github.com/cqb13");
     println!("==============================");
-    print!("\n");
+    println!();
 
     println!("Controls:");
     println!("  Move with arrows (←↑↓→)");
     println!("  Enter to select");
+    println!("  's' to save and quit");
     println!("  'q' to quit");
-    print!("\n");
+    println!();
 
     println!("Instructions:");
     println!("  Use arrows to navigate the board.");
     println!("  Press Enter to shoot at a tile.");
     println!("  First to sink all the ships wins!");
-    print!("\n");
+    println!();
 
     println!("Enjoy the game!");
     println!("==============================");
-    print!("\n");
+    println!();
 }
 
-pub fn game_options() -> GameConfig {
+// values given on the command line skip their menu
+pub fn game_options(cli_options: CliOptions) -> GameConfig {
     let mut config = GameConfig::new(GameMode::MultiPlayer, None, None);
+    config.set_record_path(cli_options.record_path.clone());
+    config.set_seed(cli_options.seed);
+    if let Some(profile) = &cli_options.profile {
+        config.set_profile(profile.clone());
+    }
 
-    let game_mode = OptionSelect::new()
-        .set_title("Game Options")
-        .add_option("Play against a friend")
-        .add_option("Play against the computer")
-        .add_option("Computer fight")
-        .ask();
-
-    print!("\n");
+    let game_mode = match cli_options.game_mode {
+        Some(game_mode) => game_mode,
+        None => loop {
+            match game_mode_options(cli_options.play_only) {
+                Some(game_mode) => break game_mode,
+                None => match GameSnapshot::load() {
+                    Ok(mut snapshot) => {
+                        if let Some(computer) = &mut snapshot.computer {
+                            computer.difficulty = computer
+                                .difficulty
+                                .with_monte_carlo_settings(cli_options.monte_carlo_settings);
+                        }
+                        config.set_saved_game(snapshot);
+                        println!("==============================");
+                        println!();
+                        return config;
+                    }
+                    Err(error) => println!("{}", error),
+                },
+            }
+        },
+    };
 
-    match game_mode.as_str() {
-        "Play against a friend" => {}
-        "Play against the computer" => {
+    match game_mode {
+        GameMode::MultiPlayer => {
+            let (board_size, fleet) =
+                board_and_fleet_options(cli_options.board_size, cli_options.fleet);
+            config.set_board_size(board_size);
+            config.set_fleet(fleet);
+        }
+        GameMode::SinglePlayer => {
             config.set_game_mode(GameMode::SinglePlayer);
-            let difficulty = difficulty_options();
-            match difficulty.as_str() {
-                "Easy" => {}
-                "Medium" => {
-                    config.set_difficulty(Difficulty::Medium);
-                }
-                "Hard" => {
-                    config.set_difficulty(Difficulty::Hard);
-                }
-                _ => {
-                    panic!("Invalid difficulty selected");
-                }
-            }
+            let (board_size, fleet) =
+                board_and_fleet_options(cli_options.board_size, cli_options.fleet);
+            config.set_board_size(board_size);
+            config.set_fleet(fleet);
+            let difficulty = cli_options
+                .difficulty
+                .unwrap_or_else(difficulty_options)
+                .with_monte_carlo_settings(cli_options.monte_carlo_settings);
+            config.set_difficulty(difficulty);
+        }
+        GameMode::NetworkHost => {
+            config.set_game_mode(GameMode::NetworkHost);
+            let (board_size, fleet) =
+                board_and_fleet_options(cli_options.board_size, cli_options.fleet);
+            config.set_board_size(board_size);
+            config.set_fleet(fleet);
+            config.set_network_address(format!(
+                "0.0.0.0:{}",
+                cli_options.port.unwrap_or(DEFAULT_PORT)
+            ));
         }
-        "Computer fight" => {
+        GameMode::NetworkJoin => {
+            config.set_game_mode(GameMode::NetworkJoin);
+            let address = cli_options.join_address.unwrap_or_else(|| {
+                TextInput::new()
+                    .set_message("Enter the address of the host, such as 127.0.0.1:7878")
+                    .ask()
+            });
+            config.set_network_address(get_join_address(address.trim()));
+        }
+        GameMode::ComputerFight => {
             config.set_game_mode(GameMode::ComputerFight);
-            let attack_strategy_one = computer_options();
-            let attack_strategy_two = computer_options();
+            let attack_strategy_one = cli_options
+                .attack_strategy_one
+                .unwrap_or_else(|| match_computer_option_to_computer(computer_options()))
+                .with_monte_carlo_settings(cli_options.monte_carlo_settings);
+            let attack_strategy_two = cli_options
+                .attack_strategy_two
+                .unwrap_or_else(|| match_computer_option_to_computer(computer_options()))
+                .with_monte_carlo_settings(cli_options.monte_carlo_settings);
 
-            let games_to_play = NumberInput::new()
-                .set_message("How many games should be played?")
-                .set_min(1)
-                .ask();
+            let games_to_play = cli_options.games_to_play.unwrap_or_else(|| {
+                NumberInput::new()
+                    .set_message("How many games should be played?")
+                    .set_min(1)
+                    .ask()
+            });
 
-            let simulation_config = SimulationConfig::new(
-                match_computer_option_to_computer(attack_strategy_one),
-                match_computer_option_to_computer(attack_strategy_two),
+            let (board_size, fleet) =
+                board_and_fleet_options(cli_options.board_size, cli_options.fleet);
+            let mut simulation_config = SimulationConfig::new(
+                attack_strategy_one,
+                attack_strategy_two,
                 games_to_play,
+                board_size,
+                fleet,
             );
+            simulation_config.record_path = cli_options.record_path;
+            simulation_config.output_path = cli_options.output_path;
+            simulation_config.seed = cli_options.seed;
 
             config.set_simulation_config(simulation_config);
         }
-        _ => {
-            panic!("Invalid game mode selected");
+        GameMode::Tournament => {
+            config.set_game_mode(GameMode::Tournament);
+            let games_per_match = cli_options.games_to_play.unwrap_or_else(|| {
+                NumberInput::new()
+                    .set_message("How many games should each match have?")
+                    .set_min(1)
+                    .ask()
+            });
+
+            let (board_size, fleet) =
+                board_and_fleet_options(cli_options.board_size, cli_options.fleet);
+            let mut tournament_config = TournamentConfig::new(games_per_match, board_size, fleet);
+            tournament_config.seed = cli_options.seed;
+            for strategy in tournament_config.strategies.iter_mut() {
+                *strategy = strategy.with_monte_carlo_settings(cli_options.monte_carlo_settings);
+            }
+
+            config.set_tournament_config(tournament_config);
+        }
+        GameMode::Benchmark => {
+            config.set_game_mode(GameMode::Benchmark);
+            let attack_strategy = cli_options
+                .benchmark_strategy
+                .unwrap_or_else(|| match_computer_option_to_computer(computer_options()))
+                .with_monte_carlo_settings(cli_options.monte_carlo_settings);
+
+            let games_to_play = cli_options.games_to_play.unwrap_or_else(|| {
+                NumberInput::new()
+                    .set_message("How many boards should be cleared?")
+                    .set_min(1)
+                    .ask()
+            });
+
+            let (board_size, fleet) =
+                board_and_fleet_options(cli_options.board_size, cli_options.fleet);
+            let mut benchmark_config =
+                BenchmarkConfig::new(attack_strategy, games_to_play, board_size, fleet);
+            benchmark_config.seed = cli_options.seed;
+
+            config.set_benchmark_config(benchmark_config);
+        }
+        GameMode::PlacementBenchmark => {
+            config.set_game_mode(GameMode::PlacementBenchmark);
+            let games_per_pairing = cli_options.games_to_play.unwrap_or_else(|| {
+                NumberInput::new()
+                    .set_message("How many boards should each pairing clear?")
+                    .set_min(1)
+                    .ask()
+            });
+
+            let (board_size, fleet) =
+                board_and_fleet_options(cli_options.board_size, cli_options.fleet);
+            let mut placement_benchmark_config =
+                PlacementBenchmarkConfig::new(games_per_pairing, board_size, fleet);
+            placement_benchmark_config.seed = cli_options.seed;
+            for strategy in placement_benchmark_config.attack_strategies.iter_mut() {
+                *strategy = strategy.with_monte_carlo_settings(cli_options.monte_carlo_settings);
+            }
+
+            config.set_placement_benchmark_config(placement_benchmark_config);
         }
     }
 
     println!("==============================");
-    print!("\n");
+    println!();
     config
 }
 
-fn difficulty_options() -> String {
-    let option = OptionSelect::new()
+// returns none when the saved game should be resumed
+fn game_mode_options(play_only: bool) -> Option<GameMode> {
+    let game_mode = OptionSelect::new()
+        .set_title("Game Options")
+        .add_option_if_true("Resume saved game".to_string(), GameSnapshot::save_exists())
+        .add_option("Play against a friend")
+        .add_option("Play against the computer")
+        .add_option_if_true("Host a network game".to_string(), !play_only)
+        .add_option_if_true("Join a network game".to_string(), !play_only)
+        .add_option_if_true("Computer fight".to_string(), !play_only)
+        .add_option_if_true("Computer tournament".to_string(), !play_only)
+        .add_option_if_true("Strategy benchmark".to_string(), !play_only)
+        .add_option_if_true("Placement benchmark".to_string(), !play_only)
+        .ask();
+
+    println!();
+
+    match game_mode.as_str() {
+        "Resume saved game" => None,
+        "Play against a friend" => Some(GameMode::MultiPlayer),
+        "Play against the computer" => Some(GameMode::SinglePlayer),
+        "Host a network game" => Some(GameMode::NetworkHost),
+        "Join a network game" => Some(GameMode::NetworkJoin),
+        "Computer fight" => Some(GameMode::ComputerFight),
+        "Computer tournament" => Some(GameMode::Tournament),
+        "Strategy benchmark" => Some(GameMode::Benchmark),
+        "Placement benchmark" => Some(GameMode::PlacementBenchmark),
+        _ => {
+            panic!("Invalid game mode selected");
+        }
+    }
+}
+
+fn difficulty_options() -> Difficulty {
+    let difficulty = OptionSelect::new()
         .set_title("Difficulty Options")
         .add_option("Easy")
         .add_option("Medium")
         .add_option("Hard")
+        .add_option("Expert")
+        .add_option("Adaptive")
         .ask();
 
-    option
+    match difficulty.as_str() {
+        "Easy" => Difficulty::Easy,
+        "Medium" => Difficulty::Medium,
+        "Hard" => Difficulty::Hard,
+        "Expert" => Difficulty::Expert(MonteCarloSettings::default()),
+        "Adaptive" => Difficulty::Adaptive,
+        _ => {
+            panic!("Invalid difficulty selected");
+        }
+    }
 }
 
-fn computer_options() -> String {
+fn board_and_fleet_options(
+    board_size: Option<BoardSize>,
+    fleet: Option<FleetDefinition>,
+) -> (BoardSize, FleetDefinition) {
+    let board_size = board_size.unwrap_or_else(board_size_options);
+
+    let fleet = match fleet {
+        Some(fleet) if check_fleet_fits(board_size, &fleet).is_ok() => fleet,
+        Some(_) => {
+            println!("That fleet does not fit on the selected board");
+            fleet_options(board_size)
+        }
+        None => fleet_options(board_size),
+    };
+
+    (board_size, fleet)
+}
+
+fn board_size_options() -> BoardSize {
     let option = OptionSelect::new()
+        .set_title("Board Size")
+        .add_option("Classic (10x10)")
+        .add_option("Custom")
+        .ask();
+
+    match option.as_str() {
+        "Classic (10x10)" => BoardSize::default(),
+        "Custom" => {
+            let width = NumberInput::new()
+                .set_message("How many columns should the board have?")
+                .set_min(MIN_BOARD_SIZE as i32)
+                .set_max(MAX_BOARD_SIZE as i32)
+                .ask();
+
+            let height = NumberInput::new()
+                .set_message("How many rows should the board have?")
+                .set_min(MIN_BOARD_SIZE as i32)
+                .set_max(MAX_BOARD_SIZE as i32)
+                .ask();
+
+            BoardSize::new(width as i8, height as i8)
+        }
+        _ => {
+            panic!("Invalid board size selected");
+        }
+    }
+}
+
+fn fleet_options(board_size: BoardSize) -> FleetDefinition {
+    loop {
+        let option = OptionSelect::new()
+            .set_title("Fleet")
+            .add_option("Classic (5, 4, 3, 3, 2)")
+            .add_option("Hasbro 1990 (5, 4, 3, 3, 2)")
+            .add_option("Russian (4, 3, 3, 2, 2, 2, 1, 1, 1, 1)")
+            .add_option("Custom")
+            .ask();
+
+        let fleet = match option.as_str() {
+            "Classic (5, 4, 3, 3, 2)" => FleetDefinition::classic(),
+            "Hasbro 1990 (5, 4, 3, 3, 2)" => FleetDefinition::hasbro_1990(),
+            "Russian (4, 3, 3, 2, 2, 2, 1, 1, 1, 1)" => FleetDefinition::russian(),
+            "Custom" => {
+                let fleet_spec = TextInput::new()
+                    .set_message("Enter the ships as name:length, separated by commas")
+                    .ask();
+
+                match FleetDefinition::parse("Custom", &fleet_spec) {
+                    Ok(fleet) => fleet,
+                    Err(error) => {
+                        println!("{}", error);
+                        continue;
+                    }
+                }
+            }
+            _ => {
+                panic!("Invalid fleet selected");
+            }
+        };
+
+        if check_fleet_fits(board_size, &fleet).is_ok() {
+            return fleet;
+        }
+
+        println!("That fleet does not fit on the selected board");
+    }
+}
+
+fn computer_options() -> String {
+    OptionSelect::new()
         .set_title("Select a Computer Attack Strategy")
         .add_option("Random Attack")
         .add_option("Hunt and Target")
         .add_option("Probability Attack")
+        .add_option("Probability Density")
+        .add_option("Monte Carlo")
         .add_option("Hacker")
-        .ask();
-
-    option
+        .ask()
 }
 
 fn match_computer_option_to_computer(strategy: String) -> ComputerAttackStrategy {
@@ -112,6 +368,8 @@ fn match_computer_option_to_computer(strategy: String) -> ComputerAttackStrategy
         "Random Attack" => ComputerAttackStrategy::Random,
         "Hunt and Target" => ComputerAttackStrategy::HuntAndTarget,
         "Probability Attack" => ComputerAttackStrategy::Probability,
+        "Probability Density" => ComputerAttackStrategy::ProbabilityDensity,
+        "Monte Carlo" => ComputerAttackStrategy::MonteCarlo(MonteCarloSettings::default()),
         "Hacker" => ComputerAttackStrategy::Hacker,
         _ => {
             panic!("Invalid computer attack strategy selected");
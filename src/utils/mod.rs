@@ -1,9 +1,20 @@
-pub mod ships;
 pub mod terminal;
 
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
-pub fn random_50_50() -> bool {
-    let mut rng = rand::thread_rng();
+/// The random number generator behind every random choice of the game, the same seed always
+/// gives the same boards and attacks.
+pub type GameRng = StdRng;
+
+/// Seeds the generator from the operating system when no seed is given.
+pub fn create_rng(seed: Option<u64>) -> GameRng {
+    match seed {
+        Some(seed) => GameRng::seed_from_u64(seed),
+        None => GameRng::from_entropy(),
+    }
+}
+
+pub fn random_50_50(rng: &mut GameRng) -> bool {
     rng.gen_bool(0.5)
 }
@@ -1,7 +1,7 @@
 use crossterm::{cursor, terminal, ExecutableCommand};
 use std::io;
 
-use crate::{Position, GRID_ARRAY_SIZE};
+use crate::{BoardSize, Position};
 
 pub enum Movement {
     Up,
@@ -10,6 +10,18 @@ pub enum Movement {
     Right,
 }
 
+/// Switches the terminal to reading single key presses, for the menus and the boards.
+///
+/// Without a terminal to read from, such as when the input is piped in, the missing setting can
+/// not be asked for, so the program exits with an error instead.
+pub fn enable_raw_mode() {
+    if let Err(error) = terminal::enable_raw_mode() {
+        eprintln!("Can not ask for input without a terminal: {}", error);
+        eprintln!("Pass every setting on the command line instead, see --help");
+        std::process::exit(1);
+    }
+}
+
 pub fn refresh_display(lines: u16) {
     for _ in 0..lines {
         io::stdout().execute(cursor::MoveUp(1)).unwrap();
@@ -26,18 +38,21 @@ pub fn move_selector_position(
     mut current_pos: Position,
     movement_direction: Movement,
     cycle_offset: i8,
+    board_size: BoardSize,
 ) -> Position {
     let y = current_pos.get_y();
     let x = current_pos.get_x();
+    let last_row = board_size.get_height() - 1;
+    let last_column = board_size.get_width() - 1;
 
     match movement_direction {
         Movement::Up if y > 0 => current_pos.set_y(y - 1),
-        Movement::Up => current_pos.set_y(GRID_ARRAY_SIZE - cycle_offset),
-        Movement::Down if y + cycle_offset < GRID_ARRAY_SIZE => current_pos.set_y(y + 1),
+        Movement::Up => current_pos.set_y(last_row - cycle_offset),
+        Movement::Down if y + cycle_offset < last_row => current_pos.set_y(y + 1),
         Movement::Down => current_pos.set_y(0),
         Movement::Left if x > 0 => current_pos.set_x(x - 1),
-        Movement::Left => current_pos.set_x(GRID_ARRAY_SIZE - cycle_offset),
-        Movement::Right if x + cycle_offset < GRID_ARRAY_SIZE => current_pos.set_x(x + 1),
+        Movement::Left => current_pos.set_x(last_column - cycle_offset),
+        Movement::Right if x + cycle_offset < last_column => current_pos.set_x(x + 1),
         Movement::Right => current_pos.set_x(0),
     }
 